@@ -1,5 +1,5 @@
 use criterion::{criterion_group, criterion_main, Criterion};
-use wordfeud_solver::{Board, Language};
+use wordfeud_solver::{Board, Language, MoveGenerator};
 
 const WORDFILE: &str = "../wordlists/wordlist-nl.txt";
 // TODO use different (generated) boards
@@ -21,12 +21,18 @@ const TEST_STATE: &[&str] = &[
     "   zerk   g    ",
 ];
 
-fn bench_calc_all_word_scores(c: &mut Criterion, name: &str, letters: &str) {
+fn bench_calc_all_word_scores(
+    c: &mut Criterion,
+    name: &str,
+    letters: &str,
+    generator: MoveGenerator,
+) {
     let board = Board::new(Language::NL)
         .with_wordlist_from_file(WORDFILE)
         .unwrap()
-        .with_state_from_strings(&TEST_STATE)
-        .unwrap();
+        .with_state_from_strings(TEST_STATE)
+        .unwrap()
+        .with_move_generator(generator);
 
     c.bench_function(&format!("board.{}", name), |b| {
         b.iter(|| {
@@ -36,13 +42,52 @@ fn bench_calc_all_word_scores(c: &mut Criterion, name: &str, letters: &str) {
     });
 }
 
+/// The inner loop of `find_best_scores`: play each candidate move and restore the board,
+/// either by setting the saved state (recalculates all rowdata) or by undoing the move.
+fn bench_play_restore(c: &mut Criterion) {
+    let mut board = Board::new(Language::NL)
+        .with_wordlist_from_file(WORDFILE)
+        .unwrap()
+        .with_state_from_strings(TEST_STATE)
+        .unwrap();
+    let mut moves = board.calc_all_word_scores("abel").unwrap();
+    moves.sort_by_key(|&item| std::cmp::Reverse(item.score));
+    moves.truncate(20);
+    let words: Vec<_> = moves
+        .iter()
+        .map(|s| (board.decode(s.word), s.x, s.y, s.horizontal))
+        .collect();
+    let saved_state = board.horizontal();
+
+    c.bench_function("board.play_set_state", |b| {
+        b.iter(|| {
+            for (word, x, y, horizontal) in &words {
+                board.play_word(word, *x, *y, *horizontal, true).unwrap();
+                board.set_state(&saved_state);
+            }
+        })
+    });
+    c.bench_function("board.play_undo", |b| {
+        b.iter(|| {
+            for (word, x, y, horizontal) in &words {
+                board.play_word(word, *x, *y, *horizontal, true).unwrap();
+                board.undo();
+            }
+        })
+    });
+}
+
 fn criterion_benchmark(c: &mut Criterion) {
-    bench_calc_all_word_scores(c, "1", "abel");
+    bench_calc_all_word_scores(c, "1", "abel", MoveGenerator::Trie);
+    bench_calc_all_word_scores(c, "gaddag.1", "abel", MoveGenerator::Gaddag);
+    bench_play_restore(c);
 }
 
 fn slow_benchmarks(c: &mut Criterion) {
-    bench_calc_all_word_scores(c, "2", "mdjenj*");
-    bench_calc_all_word_scores(c, "3", "polkas*");
+    bench_calc_all_word_scores(c, "2", "mdjenj*", MoveGenerator::Trie);
+    bench_calc_all_word_scores(c, "3", "polkas*", MoveGenerator::Trie);
+    bench_calc_all_word_scores(c, "gaddag.2", "mdjenj*", MoveGenerator::Gaddag);
+    bench_calc_all_word_scores(c, "gaddag.3", "polkas*", MoveGenerator::Gaddag);
 }
 
 criterion_group! {
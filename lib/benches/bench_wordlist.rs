@@ -1,5 +1,5 @@
 use criterion::{criterion_group, criterion_main, Criterion};
-use wordfeud_solver::{Codec, RowData, Wordlist};
+use wordfeud_solver::{Codec, Gaddag, RowData, Wordlist};
 
 const WORDS: &[&str] = &[
     "af", "ah", "al", "aar", "aas", "bi", "bo", "bar", "bes", "bel",
@@ -15,6 +15,20 @@ fn bench_from_file() {
     let _wordlist = Wordlist::from_file(WORDFILE_SMALL, &Codec::default()).unwrap();
 }
 
+fn bench_build(c: &mut Criterion, wordfile: &str) {
+    let text = std::fs::read_to_string(wordfile).unwrap();
+    let mut words: Vec<&str> = text.lines().map(str::trim).collect();
+    words.sort_unstable();
+    let codec = Codec::default();
+    c.bench_function("wordlist.build.sorted", |b| {
+        b.iter(|| Wordlist::from_words(&words, &codec).unwrap())
+    });
+    words.reverse();
+    c.bench_function("wordlist.build.unsorted", |b| {
+        b.iter(|| Wordlist::from_words(&words, &codec).unwrap())
+    });
+}
+
 fn bench_words(
     c: &mut Criterion,
     name: &str,
@@ -28,7 +42,27 @@ fn bench_words(
     c.bench_function(name, |b| {
         b.iter(|| {
             wordlist
-                .words(&row, &rowdata, &letters, None)
+                .words(&row, rowdata, &letters, None)
+                .collect::<Vec<_>>()
+        })
+    });
+}
+
+fn bench_gaddag_words(
+    c: &mut Criterion,
+    name: &str,
+    wordlist: &Wordlist,
+    row: &str,
+    rowdata: &RowData,
+    letters: &str,
+) {
+    let gaddag = Gaddag::from(wordlist);
+    let row = wordlist.encode(row).unwrap();
+    let letters = wordlist.encode(letters).unwrap();
+    c.bench_function(name, |b| {
+        b.iter(|| {
+            gaddag
+                .words(&row, rowdata, &letters, None)
                 .collect::<Vec<_>>()
         })
     });
@@ -61,6 +95,7 @@ fn bench_node_matches(c: &mut Criterion, name: &str, wordfile: &str, letters: &s
 fn criterion_benchmark(c: &mut Criterion) {
     c.bench_function("wordlist.from_words", |b| b.iter(bench_from_words));
     c.bench_function("wordlist.from_file", |b| b.iter(bench_from_file));
+    bench_build(c, WORDFILE);
 
     let wordlist = Wordlist::from_file(WORDFILE, &Codec::default()).unwrap();
     let labels = wordlist.all_labels;
@@ -68,6 +103,10 @@ fn criterion_benchmark(c: &mut Criterion) {
     let row = "    t     c   f";
     let letters = "polkas*";
     let wordlist = Wordlist::from_file(WORDFILE, &Codec::default()).unwrap();
+    bench_gaddag_words(c, "gaddag.words.1", &wordlist, row, &rowdata, letters);
+    c.bench_function("gaddag.from_wordlist", |b| {
+        b.iter(|| Gaddag::from(&wordlist))
+    });
     bench_words(c, "wordlist.words.1", wordlist, row, &rowdata, letters);
     bench_get_legal_characters(c);
     bench_node_matches(c, "node.matches.1", WORDFILE, "abel");
@@ -21,7 +21,7 @@ const TEST_STATE: &[&str] = &[
 
 fn run() -> Result<()> {
     let board = Board::new(Language::EN)
-        .with_state_from_strings(&TEST_STATE)?
+        .with_state_from_strings(TEST_STATE)?
         .with_wordlist_from_file("../wordlists/wordlist-en-sowpods.txt")?;
     let letters = board.encode("koetsje")?;
     board.calc_all_word_scores(letters)?;
@@ -40,9 +40,9 @@ fn run() -> Result<()> {
         let indices = wordlist.start_indices(row, rowdata, maxdist);
         // println!("{} {:?}", i, indices);
 
-        for j in 0..row.len() {
-            if state_str[i][j] == '.' && indices.contains(&j) {
-                state_str[i][j] = '+'
+        for (j, c) in state_str[i].iter_mut().enumerate().take(row.len()) {
+            if *c == '.' && indices.contains(&j) {
+                *c = '+'
             }
         }
     }
@@ -61,9 +61,9 @@ fn run() -> Result<()> {
         .zip(board.rowdata(true))
         .enumerate()
     {
-        for j in 0..row.len() {
-            if state_str[i][j] == '.' && rowdata[j].1 {
-                state_str[i][j] = '*'
+        for (j, c) in state_str[i].iter_mut().enumerate().take(row.len()) {
+            if *c == '.' && rowdata[j].1 {
+                *c = '*'
             }
         }
     }
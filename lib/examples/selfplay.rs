@@ -0,0 +1,32 @@
+use anyhow::Result;
+use std::env;
+use std::time::Instant;
+use wordfeud_solver::{play_match, Board, Greedy, Language, SearchConfig, Searched};
+
+fn run() -> Result<()> {
+    let mut args = env::args().skip(1);
+    let games: usize = args.next().map_or(Ok(100), |arg| arg.parse())?;
+    let wordfile = args
+        .next()
+        .unwrap_or_else(|| String::from("../wordlists/wordlist-nl.txt"));
+    let board = Board::new(Language::NL).with_wordlist_from_file(&wordfile)?;
+    let best_scores = Searched {
+        config: SearchConfig {
+            samples: 20,
+            ..SearchConfig::default()
+        },
+    };
+
+    eprintln!("Playing {} games: find_best_scores vs greedy", games);
+    let now = Instant::now();
+    let report = play_match(&board, &best_scores, &Greedy, games, 0)?;
+    eprintln!("took {:.1} s", now.elapsed().as_secs_f32());
+    println!("{}", report);
+    Ok(())
+}
+
+fn main() {
+    if let Err(err) = run() {
+        eprintln!("Error: {:?}", err);
+    }
+}
@@ -0,0 +1,57 @@
+use anyhow::Result;
+use std::env;
+use std::fs::File;
+use std::time::Instant;
+use wordfeud_solver::{Board, Greedy, Language, Pairing, SearchConfig, Searched, Tournament};
+
+fn best_scores(samples: usize) -> Searched {
+    Searched {
+        config: SearchConfig {
+            samples,
+            ..SearchConfig::default()
+        },
+    }
+}
+
+fn run() -> Result<()> {
+    let mut args = env::args().skip(1);
+    let games: usize = args.next().map_or(Ok(10), |arg| arg.parse())?;
+    let wordfile = args
+        .next()
+        .unwrap_or_else(|| String::from("../wordlists/wordlist-nl.txt"));
+    let prefix = args.next().unwrap_or_else(|| String::from("tournament"));
+    let board = Board::new(Language::NL).with_wordlist_from_file(&wordfile)?;
+    let tournament = Tournament::new(Pairing::RoundRobin, games, 0)
+        .with_entrant("greedy", Greedy)
+        .with_entrant("samples-5", best_scores(5))
+        .with_entrant("samples-20", best_scores(20))
+        .with_entrant("samples-50", best_scores(50));
+
+    eprintln!("Playing round robin with {} games per pairing", games);
+    let now = Instant::now();
+    let result = tournament.run(&board)?;
+    eprintln!("took {:.1} s", now.elapsed().as_secs_f32());
+    for s in result.ranking() {
+        println!(
+            "{:12} games: {:3} wins: {:5.1} spread: {:6} elo: {:6.1} glicko: {:6.1} ± {:5.1}",
+            s.name,
+            s.games,
+            s.wins,
+            s.spread,
+            s.elo,
+            s.glicko.rating,
+            2.0 * s.glicko.deviation
+        );
+    }
+    result.write_games_csv(File::create(format!("{}-games.csv", prefix))?)?;
+    result.write_standings_csv(File::create(format!("{}-standings.csv", prefix))?)?;
+    #[cfg(feature = "serde")]
+    serde_json::to_writer_pretty(File::create(format!("{}.json", prefix))?, &result)?;
+    Ok(())
+}
+
+fn main() {
+    if let Err(err) = run() {
+        eprintln!("Error: {:?}", err);
+    }
+}
@@ -0,0 +1,33 @@
+use anyhow::{bail, Result};
+use std::env;
+use std::time::Instant;
+use wordfeud_solver::{Board, Language, Wordlist};
+
+/// Convert a text wordlist to a binary wordlist file that can be memory-mapped.
+///
+/// Usage: `cargo run --features mmap --example wordfile -- <words.txt> <wordlist.bin> [en|nl|se]`
+fn main() -> Result<()> {
+    let mut args = env::args().skip(1);
+    let (wordfile, binfile) = match (args.next(), args.next()) {
+        (Some(wordfile), Some(binfile)) => (wordfile, binfile),
+        _ => bail!("usage: wordfile <words.txt> <wordlist.bin> [en|nl|se]"),
+    };
+    let language = match args.next().as_deref() {
+        None | Some("nl") => Language::NL,
+        Some("en") => Language::EN,
+        Some("se") => Language::SE,
+        Some(other) => bail!("unknown language {}", other),
+    };
+    let board = Board::new(language);
+
+    let t = Instant::now();
+    let wordlist = Wordlist::from_file(&wordfile, board.codec())?;
+    eprintln!("{} in {:?}", wordlist, t.elapsed());
+    wordlist.write_mapped_file(&binfile)?;
+
+    // validate the result
+    let t = Instant::now();
+    let mapped = Wordlist::from_mapped_file(&binfile, board.codec())?;
+    eprintln!("{} in {:?}", mapped, t.elapsed());
+    Ok(())
+}
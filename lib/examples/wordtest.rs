@@ -27,7 +27,7 @@ fn run() -> Result<()> {
         Board::new(Language::NL).with_wordlist_deserialize_from("../wordlists/wordlist-nl.bin")?;
     #[cfg(not(feature = "bincode"))]
     let board = Board::new(Language::NL).with_wordlist_from_file("../wordfeud-wordlists/nl/wordlist-nl.txt")?;
-    let board = board.with_state_from_strings(&TEST_STATE)?;
+    let board = board.with_state_from_strings(TEST_STATE)?;
     let dt = t0.elapsed();
     println!("Create board with wordlist took {:?}", dt);
     let letters = "koetsje";
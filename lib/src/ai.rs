@@ -1,9 +1,29 @@
 #![allow(dead_code, unused_variables, unused_mut, unused_assignments)]
+use self::preendgame::{rack_splits, RackSplit};
+use crate::game::{Move, BINGO_BONUS, RACK_SIZE};
 use crate::tilebag::TileBag;
 use crate::tiles::BLANK;
-use crate::{Board, Code, Error, Item, Letter, Letters, TileSet, List};
+use crate::{Board, Code, Error, Item, Letter, Letters, List, TileSet};
 use rand::{rngs::StdRng, seq::IteratorRandom, Rng, SeedableRng};
 use std::convert::{From, TryFrom};
+use std::time::Instant;
+
+mod bingo;
+mod config;
+mod defense;
+mod endgame;
+mod inference;
+mod leave;
+mod preendgame;
+mod simulate;
+
+pub use self::bingo::{bingo_probability, has_bingo};
+pub use self::config::SearchConfig;
+pub use self::defense::{evaluate_risks, Exposure};
+pub use self::endgame::{solve_endgame, EndgameLimits, EndgameMove, EndgameResult};
+pub use self::inference::OpponentModel;
+pub use self::leave::{leave_samples, LeaveTable};
+pub use self::simulate::{simulate, SimScore, Simulation};
 
 #[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[derive(Debug, Clone, Copy)]
@@ -12,6 +32,30 @@ pub enum ExitFlag {
     Our = 1,
     Opponent = 2,
 }
+/// Kind of move in a [`Score`]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MoveKind {
+    /// Play a word on the board
+    Word = 0,
+    /// Swap the `played` letters with tiles from the bag
+    Swap = 1,
+    /// Pass the turn
+    Pass = 2,
+}
+
+/// Number of refills drawn to evaluate a swap
+const SWAP_SAMPLES: usize = 10;
+
+/// Maximum number of swaps returned by `find_best_scores`
+const MAX_SWAPS: usize = 5;
+
+/// Streams of random draws in `find_best_scores`, see [`derived_rng`]
+const OPPONENT_STREAM: u64 = 0;
+const SWAP_STREAM: u64 = 1;
+const BINGO_STREAM: u64 = 2;
+const SWAP_BINGO_STREAM: u64 = 3;
+
 /// Returned score information. Extended from [board::Score](crate::Score)
 #[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[derive(Debug, Clone)]
@@ -38,14 +82,134 @@ pub struct Score {
     pub exit_flag: ExitFlag,
     /// difference in expected opponent move value caused by our move
     pub opp_score_diff: i32,
+    /// word or swap. For a swap the swapped letters are in `played`, and `word` is empty
+    pub kind: MoveKind,
+    /// value of the letters left on our rack after the move, see [`LeaveTable`]
+    pub leave_score: f32,
+    /// increase of the risk of the board by the move, see [`Exposure`]
+    pub risk: f32,
+    /// expected bingo bonus of our next turn: the probability that the leave with a random refill
+    /// has a bingo on the board after the move, times the bingo bonus
+    pub bingo_bonus: f32,
+    /// for a swap: our mean best score with the refilled rack, minus our best score with the current rack.
+    /// 0 for other moves. It is included in [`Ranking::Equity`]
+    pub swap_gain: f32,
+}
+
+impl Score {
+    /// Return the value of the move for `ranking`.
+    pub fn value(&self, ranking: Ranking) -> f32 {
+        match ranking {
+            Ranking::Score => self.score as f32,
+            Ranking::AdjScore => self.adj_score as f32,
+            Ranking::Equity => {
+                self.adj_score as f32 + self.leave_score + self.bingo_bonus + self.swap_gain
+            }
+            Ranking::Defensive => self.score as f32 + self.leave_score - self.risk,
+        }
+    }
+
+    /// Return the move to play on `board`.
+    /// ## Errors
+    /// If the word or played letters can not be encoded.
+    pub fn to_move(&self, board: &Board) -> Result<Move, Error> {
+        Ok(match self.kind {
+            MoveKind::Word => {
+                let word = board.encode(&self.word)?;
+                Move::Tiles(board.word_placement(word, self.x, self.y, self.horizontal))
+            }
+            MoveKind::Swap => Move::Swap(board.encode(&self.played)?),
+            MoveKind::Pass => Move::Pass,
+        })
+    }
+}
+
+/// How to rank the results of [`find_best_scores`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Ranking {
+    /// the points of the move
+    Score,
+    /// the points of the move, adjusted for the opponent score
+    AdjScore,
+    /// the adjusted score plus the value of the leave and the expected bingo bonus,
+    /// and for a swap the sampled `swap_gain`
+    Equity,
+    /// the points of the move plus the value of the leave, minus the risk.
+    /// This needs no opponent scores
+    Defensive,
+}
+
+/// Sort `scores` from best to worst by `ranking`.
+pub fn rank_scores(scores: &mut [Score], ranking: Ranking) {
+    scores.sort_by(|a, b| {
+        b.value(ranking)
+            .total_cmp(&a.value(ranking))
+            .then_with(|| b.score.cmp(&a.score))
+            .then_with(|| {
+                let key = |s: &Score| (s.y, s.x, !s.horizontal, s.kind as u8);
+                key(a).cmp(&key(b))
+            })
+            .then_with(|| a.word.cmp(&b.word))
+            .then_with(|| a.played.cmp(&b.played))
+    });
+}
+
+/// Set the `leave_score` of each of `scores` to the value of the leave in `table`,
+/// given our letters in `rack`.
+/// ## Errors
+/// - If the played letters of a score can not be encoded.
+/// - [`LettersNotInRack`](Error::LettersNotInRack) if the played letters of a score are not in `rack`.
+pub fn evaluate_leaves(
+    board: &Board,
+    rack: Letters,
+    scores: &mut [Score],
+    table: &LeaveTable,
+) -> Result<(), Error> {
+    for s in scores {
+        let played: Letters = board.encode(&s.played)?;
+        let leave = rack
+            .remove_letters(played)
+            .map_err(|missing| Error::LettersNotInRack(board.decode(missing)))?;
+        s.leave_score = table.evaluate(leave);
+    }
+    Ok(())
+}
+
+/// Return the score of word `s` on `board`, without opponent scores.
+///
+/// This converts the results of [calc_all_word_scores](Board::calc_all_word_scores) without sampling,
+/// for example to rank them with [`evaluate_leaves`] or [`evaluate_risks`].
+pub fn word_score(board: &Board, s: &crate::Score) -> Score {
+    let played: Letters = board
+        .word_placement(s.word, s.x, s.y, s.horizontal)
+        .iter()
+        .map(|&(_, _, tile)| Letter::from_tile(tile))
+        .collect();
+    Score {
+        x: s.x,
+        y: s.y,
+        horizontal: s.horizontal,
+        word: board.decode(s.word),
+        score: s.score as i32,
+        adj_score: s.score as i32,
+        opp_score: 0,
+        opp_std: 0.0,
+        played: board.decode(played),
+        exit_flag: ExitFlag::None,
+        opp_score_diff: 0,
+        kind: MoveKind::Word,
+        leave_score: 0.0,
+        risk: 0.0,
+        bingo_bonus: 0.0,
+        swap_gain: 0.0,
+    }
 }
 
 pub fn used_tiles(board: &Board, rack: Letters) -> TileBag {
     let mut used_tiles: Vec<_> = board
         .horizontal()
         .iter()
-        .map(|&row| row.into_iter())
-        .flatten()
+        .flat_map(|&row| row.into_iter())
         .filter_map(|cell| cell.tile())
         .map(|tile| {
             if tile.is_wildcard() {
@@ -63,7 +227,7 @@ pub fn remaining_tiles(full_bag: &TileBag, board: &Board, rack: Letters) -> Tile
     full_bag.clone() - used_tiles(board, rack)
 }
 
-fn tiles_score(tiles: &Letters, tileset: &TileSet) -> i32 {
+pub(crate) fn tiles_score(tiles: &Letters, tileset: &TileSet) -> i32 {
     let score: u32 = tiles
         .into_iter()
         .map(|letter| tileset.points(letter.code()))
@@ -71,6 +235,20 @@ fn tiles_score(tiles: &Letters, tileset: &TileSet) -> i32 {
     score as i32
 }
 
+/// Return the random generator for draw `index` of `stream`, derived from `seed`.
+///
+/// Each draw has its own generator, so the draws do not depend on the order in which they are made,
+/// or on the number of threads.
+pub(crate) fn derived_rng(seed: u64, stream: u64, index: u64) -> StdRng {
+    // splitmix64
+    let mut z = seed
+        .wrapping_add(stream.wrapping_mul(0xbf58_476d_1ce4_e5b9))
+        .wrapping_add(index.wrapping_add(1).wrapping_mul(0x9e37_79b9_7f4a_7c15));
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    StdRng::seed_from_u64(z ^ (z >> 31))
+}
+
 fn draw_random_tiles<R: Rng>(tiles: &[Code], n: usize, mut rng: &mut R) -> Vec<Code> {
     tiles
         .iter()
@@ -87,6 +265,10 @@ fn draw_random_tiles<R: Rng>(tiles: &[Code], n: usize, mut rng: &mut R) -> Vec<C
 ///
 /// ## MIDGAME: tiles left in bag
 ///
+/// This uses the default [`SearchConfig`] with `nsamples` samples, without swaps and bingo estimates,
+/// see [`find_best_scores_with`] for the meaning of the parameters.
+/// Only words are returned, see [`find_best_scores_with`] to also get passes and swaps.
+///
 /// For each of the 20 best words:
 /// - Play the word on the board.
 /// - For each of `nsamples` randow draws of 7 tiles from the remaining tiles:
@@ -94,8 +276,53 @@ fn draw_random_tiles<R: Rng>(tiles: &[Code], n: usize, mut rng: &mut R) -> Vec<C
 ///     - Add the best score to a list of opponent tiles score
 /// - Calculate the mean opponent score. Subtract it from our score
 ///
+/// ## PRE-ENDGAME: 1 to 7 tiles left in bag
+///
+/// Instead of random draws, the opponent scores are calculated for every possible
+/// division of the remaining tiles between the opponent rack and the bag,
+/// weighted by the probability of that division.
+/// - If our word uses at least as many letters as there are in the bag, we empty the bag,
+///   and the opponent can exit with the next move. The opponent then gets the value of our letters.
+/// - A pass is returned with [`MoveKind::Pass`], only by [`find_best_scores_with`].
+///
+/// ## SWAPS: at least 7 tiles left in bag
+///
+/// Only with [`find_best_scores_with`] and `swaps` set in the config.
+///
+/// A swap scores no points, so like for a word the adjusted score is its score (0) minus
+/// the mean opponent score if we pass, and the `leave_score` is the value of the letters we keep.
+///
+/// For each distinct subset of our letters:
+/// - For each of 10 random refills of the swapped letters from the remaining tiles:
+///     - Calculate our best score with the new letters.
+/// - The `swap_gain` is the mean best score with the new letters,
+///   minus our best score with the current letters.
+///
+/// The `swap_gain` is in points, like the score of a word, and it is included in [`Ranking::Equity`].
+/// The 5 best swaps by [`Ranking::Equity`] are returned with [`MoveKind::Swap`].
+///
+/// ## BINGO: at least 8 tiles left in bag
+///
+/// Only with [`find_best_scores_with`] and `bingo_samples` set in the config.
+///
+/// For each word and swap, the `bingo_bonus` is the expected bingo bonus of our next turn:
+/// - For each of `bingo_samples` random refills of the letters left on our rack to a full rack:
+///     - Check if a word with all letters can be played from an anchor of the board after our move.
+/// - The fraction of refills with a bingo, times the bingo bonus of [`BINGO_BONUS`](crate::BINGO_BONUS) points.
+///
+/// The opponent move in between is ignored. The bingo bonus is included in [`Ranking::Equity`].
+///
+/// ## LEAVE
+///
+/// The `leave_score` of each move is the value of the letters left on our rack,
+/// according to the default [`LeaveTable`] for the language of the board.
+/// Use [`rank_scores`] to sort the results with the leave value included,
+/// and [`evaluate_leaves`] to use another table.
+///
 /// ## ENDGAME: no tiles left in bag
 ///
+/// This is a one-ply heuristic, see [`solve_endgame`] for an exact search.
+///
 /// For each of our words:
 /// - Play the word on the board.
 ///     - CASE 1:
@@ -116,74 +343,196 @@ pub fn find_best_scores(
     rack: Letters,
     nsamples: usize,
 ) -> Result<Vec<Score>, Error> {
+    let config = SearchConfig {
+        samples: nsamples,
+        swaps: false,
+        bingo_samples: 0,
+        ..SearchConfig::default()
+    };
+    let mut scores = find_best_scores_with(board, rack, &config)?;
+    // passes and swaps are only returned by `find_best_scores_with`
+    scores.retain(|s| s.kind == MoveKind::Word);
+    Ok(scores)
+}
+
+/// Find the best moves on a wordfeud board, considering opponent moves, with the parameters in `config`.
+///
+/// See [`find_best_scores`] for a description of the search. With `config`:
+/// - the random draws are derived from `seed`, or from system entropy. Each draw has its own generator,
+///   and ties are broken by a total order, so the results do not depend on the number of `threads`,
+/// - `candidates` of our best words are evaluated, instead of 20,
+/// - `samples` random opponent racks of `rack_size` tiles are drawn, instead of `nsamples` racks of 7 tiles,
+/// - the opponent racks are drawn from the `opponent` model, if set,
+/// - no more words are evaluated after `time_budget`, and then swaps are skipped,
+/// - swaps are only evaluated if `swaps` is true,
+/// - the opponent scores are calculated with `threads` threads,
+/// - the leaves are evaluated with the `leave` table,
+/// - the bingo probability of each leave is estimated with `bingo_samples` refills, if it is not 0,
+/// - in the pre-endgame only the `max_rack_splits` most probable opponent racks are evaluated, if it is set.
+///
+/// # Errors
+/// See [`find_best_scores`].
+/// Also fails if the thread pool with `threads` threads can not be created.
+pub fn find_best_scores_with(
+    board: &mut Board,
+    rack: Letters,
+    config: &SearchConfig,
+) -> Result<Vec<Score>, Error> {
+    with_threads(config.threads, || search(board, rack, config))
+}
+
+/// Run `f` in a thread pool with `threads` threads, or in the current thread pool if `threads` is `None`.
+fn with_threads<T, F>(threads: Option<usize>, f: F) -> Result<T, Error>
+where
+    T: Send,
+    F: FnOnce() -> Result<T, Error> + Send,
+{
+    #[cfg(feature = "rayon")]
+    {
+        if let Some(threads) = threads {
+            let pool = rayon::ThreadPoolBuilder::new()
+                .num_threads(threads)
+                .build()
+                .map_err(|err| Error::ThreadPoolError(err.to_string()))?;
+            return pool.install(f);
+        }
+    }
+    f()
+}
+
+fn search(board: &mut Board, rack: Letters, config: &SearchConfig) -> Result<Vec<Score>, Error> {
     let mut result = Vec::new();
-    let mut rng = StdRng::seed_from_u64(123); // seeded to get reproducible results.
+    let start = Instant::now();
+    let out_of_time = || {
+        config
+            .time_budget
+            .is_some_and(|budget| start.elapsed() >= budget)
+    };
+    let seed = config.seed.unwrap_or_else(|| StdRng::from_entropy().gen());
+    let default_table;
+    let table = match &config.leave {
+        Some(table) => table,
+        None => {
+            default_table = LeaveTable::new(board.tileset().language.clone());
+            &default_table
+        }
+    };
     let full_bag = TileBag::from(board.tileset());
     let remaining = remaining_tiles(&full_bag, board, rack);
-    let mut tiles: Vec<u8> = remaining.iter().cloned().collect(); // remaining tiles as Vec<Code>
-    tiles.sort_unstable();
+    let tiles = remaining.sorted();
+
+    let mut opp_tiles_score: i32 = 0;
+    let in_endgame = remaining.len() <= 7;
+    let bag_len = remaining.len().saturating_sub(RACK_SIZE);
+    let in_pre_endgame = (1..=RACK_SIZE).contains(&bag_len);
+    // we can swap if there are 7 tiles in the bag, besides the 7 tiles of the opponent
+    let can_swap = remaining.len() >= 14;
 
     // calculate word scores for our letters
     let mut words = board.calc_all_word_scores(rack)?;
-    if words.is_empty() {
+    // in the pre-endgame passing is evaluated, even without words
+    if words.is_empty() && !can_swap && !in_pre_endgame {
         return Ok(result);
     }
-    words.sort_by_key(|item| std::cmp::Reverse(item.score));
-
-    let mut opp_tiles_score: i32 = 0;
-    let in_endgame = remaining.len() <= 7;
+    words.sort_by(|a, b| b.cmp(a));
 
     // In endgame the opponent letters are known, calculate all possible opponent moves.
     // Otherwise, prepare a bunch of random samples from remaining letters and calculate best opponent moves with each
     let samples: Vec<Letters>;
-    let top_n; // number of our best scores to evaluate: all in endgame, otherwise 20
+    let mut splits: Vec<RackSplit> = Vec::new(); // opponent racks with probability in pre-endgame
+    let top_n; // number of our best scores to evaluate: all in endgame, otherwise `candidates`
     if in_endgame {
         // one sample
-        let sample = Letters::try_from(tiles)?; // remaining tiles
+        let sample = Letters::try_from(tiles.clone())?; // remaining tiles
         samples = vec![sample];
         opp_tiles_score = tiles_score(&sample, board.tileset());
         top_n = words.len(); // evaluate all our possible words in endgame
-        println!("in endgame, top_n: {}, remaining: {:?}, opp tiles score {}", top_n, board.decode(sample), opp_tiles_score);
+    } else if in_pre_endgame {
+        // all possible opponent racks, or the most probable if they are limited
+        splits = rack_splits(&tiles, config.max_rack_splits)?;
+        samples = splits.iter().map(|split| split.rack).collect();
+        top_n = config.candidates;
     } else {
-        // random samples from remaining letters
-        samples = (0..nsamples)
-            .into_iter()
-            .map(|_| {
-                let v = draw_random_tiles(&tiles, 7, &mut rng);
-                Letters::try_from(v).unwrap()
+        // random samples from remaining letters, or from the opponent model
+        samples = (0..config.samples)
+            .map(|i| {
+                let mut rng = derived_rng(seed, OPPONENT_STREAM, i as u64);
+                match &config.opponent {
+                    Some(model) => Ok(model.sample(1, config.rack_size, &mut rng)?[0]),
+                    None => {
+                        Letters::try_from(draw_random_tiles(&tiles, config.rack_size, &mut rng))
+                    }
+                }
             })
-            .collect();
-        top_n = 20; // evaluate up to 20 of our best words
+            .collect::<Result<_, _>>()?;
+        top_n = config.candidates;
     }
+    let in_midgame = !in_endgame && !in_pre_endgame;
     // what is the expected opponent value if we pass or swap?
-    let no_move_opp_scores: Vec<_> = board.sample_scores(&samples, 0, false)?
-        .iter().map(|&(score,_)| score as i32).collect();
-    let no_move_mean_opp_score = mean(&no_move_opp_scores).unwrap_or(0.0);
-    // println!("no_move_mean_opp_score: {:.1}", no_move_mean_opp_score);
+    let no_move_opp_scores: Vec<_> = board
+        .sample_scores(&samples, 0, false)?
+        .iter()
+        .map(|&(score, _)| score as i32)
+        .collect();
+    let (no_move_mean_opp_score, no_move_std_opp_score) = stats(&no_move_opp_scores, &splits);
 
-    let saved_state = board.horizontal();
     for (i, &s) in words.iter().take(top_n).enumerate() {
-        let letters = board.decode(s.word);
-        let played = board.play_word(&letters, s.x, s.y, s.horizontal, true)?;
-        let mut exit_flag = ExitFlag::None;
-        let mut opp_scores = Vec::new();
-        if in_endgame && played.len() == rack.len() {
-            // we have a winning move, and opponent tiles value will be added to our score
-            // TODO CHECK if this works for non-ascii or multichar tiles
-            exit_flag = ExitFlag::Our;
-            opp_scores.push(-opp_tiles_score);
-        } else {
-            let res = board.sample_scores(&samples, s.score, false)?;
-            opp_scores = res.iter().map(|&(score, _)| score as i32).collect();
-            if res.iter().any(|&(_, opp_exit)| opp_exit) {
-                exit_flag = ExitFlag::Opponent;
-            }
+        if i > 0 && out_of_time() {
+            break;
         }
+        // the move is placed without the move history, so the moves of the user can still be undone
+        let placement = board.word_placement(s.word, s.x, s.y, s.horizontal);
+        let (play, record) = board.place(&placement)?;
+        let used = play.used;
+        let evaluate = |board: &Board| -> Result<(ExitFlag, Vec<i32>, f32), Error> {
+            let mut exit_flag = ExitFlag::None;
+            let mut opp_scores = Vec::new();
+            if in_endgame && used.len() == rack.len() {
+                // we have a winning move, and opponent tiles value will be added to our score
+                exit_flag = ExitFlag::Our;
+                opp_scores.push(-opp_tiles_score);
+            } else if in_pre_endgame && used.len() >= bag_len {
+                // we empty the bag, the opponent can exit and get the value of our letters
+                let leave = rack
+                    .remove_letters(used)
+                    .map_err(|missing| Error::LettersNotInRack(board.decode(missing)))?;
+                let leave_tiles_score = tiles_score(&leave, board.tileset());
+                for split in &splits {
+                    let our_tiles_score =
+                        leave_tiles_score + tiles_score(&split.bag, board.tileset());
+                    let (score, opp_exit) =
+                        board.sample_scores(&[split.rack], our_tiles_score as u32, true)?[0];
+                    opp_scores.push(score as i32);
+                    if opp_exit {
+                        exit_flag = ExitFlag::Opponent;
+                    }
+                }
+            } else {
+                let res = board.sample_scores(&samples, s.score, false)?;
+                opp_scores = res.iter().map(|&(score, _)| score as i32).collect();
+                if res.iter().any(|&(_, opp_exit)| opp_exit) {
+                    exit_flag = ExitFlag::Opponent;
+                }
+            }
 
-        board.set_state(&saved_state); // restore board before next iteration
+            let bingo_bonus = if in_midgame {
+                let leave = rack
+                    .remove_letters(used)
+                    .map_err(|missing| Error::LettersNotInRack(board.decode(missing)))?;
+                let mut rng = derived_rng(seed, BINGO_STREAM, i as u64);
+                BINGO_BONUS as f32
+                    * bingo_probability(board, leave, &tiles, config.bingo_samples, &mut rng)?
+            } else {
+                0.0
+            };
+            Ok((exit_flag, opp_scores, bingo_bonus))
+        };
+        let evaluated = evaluate(board);
+        board.unplace(&record); // restore board before next iteration
+        let (exit_flag, opp_scores, bingo_bonus) = evaluated?;
+        let played = board.decode(used);
 
-        let mean_opp_score = mean(&opp_scores).unwrap_or(0.0); // 0 if no moves for opponent
-        let std_opp_score = std_deviation(&opp_scores).unwrap_or(0.0);
+        let (mean_opp_score, std_opp_score) = stats(&opp_scores, &splits); // 0 if no moves for opponent
         let adj_opp_score = s.score as f32 - mean_opp_score;
         let res = Score {
             x: s.x,
@@ -197,13 +546,138 @@ pub fn find_best_scores(
             played,
             exit_flag,
             opp_score_diff: (no_move_mean_opp_score - mean_opp_score).round() as i32,
+            kind: MoveKind::Word,
+            leave_score: 0.0,
+            risk: 0.0,
+            bingo_bonus,
+            swap_gain: 0.0,
         };
         result.push(res);
     }
 
+    if in_pre_endgame {
+        result.push(Score {
+            x: 0,
+            y: 0,
+            horizontal: true,
+            word: String::new(),
+            score: 0,
+            adj_score: -no_move_mean_opp_score.round() as i32,
+            opp_score: no_move_mean_opp_score.round() as i32,
+            opp_std: no_move_std_opp_score,
+            played: String::new(),
+            exit_flag: ExitFlag::None,
+            opp_score_diff: 0,
+            kind: MoveKind::Pass,
+            leave_score: 0.0,
+            risk: 0.0,
+            bingo_bonus: 0.0,
+            swap_gain: 0.0,
+        });
+    }
+
+    if can_swap && config.swaps && !out_of_time() {
+        let best_score = words.first().map_or(0, |s| s.score) as f32;
+        let mut swaps = Vec::new();
+        for (k, swapped) in swap_candidates(rack).into_iter().enumerate() {
+            let keep = rack
+                .remove_letters(swapped)
+                .map_err(|missing| Error::LettersNotInRack(board.decode(missing)))?;
+            let refills: Vec<Letters> = (0..SWAP_SAMPLES)
+                .map(|j| {
+                    let mut rng = derived_rng(seed, SWAP_STREAM, (k * SWAP_SAMPLES + j) as u64);
+                    let mut letters = keep;
+                    for code in draw_random_tiles(&tiles, swapped.len(), &mut rng) {
+                        letters.push(Letter::try_from(code)?);
+                    }
+                    Ok(letters)
+                })
+                .collect::<Result<_, Error>>()?;
+            let our_scores: Vec<_> = board
+                .sample_scores(&refills, 0, false)?
+                .iter()
+                .map(|&(score, _)| score as i32)
+                .collect();
+            let gain = mean(&our_scores).unwrap_or(0.0) - best_score;
+            // the bingo bonus is part of the equity, so evaluate it before the best swaps are selected
+            let mut rng = derived_rng(seed, SWAP_BINGO_STREAM, k as u64);
+            let bingo_bonus = BINGO_BONUS as f32
+                * bingo_probability(board, keep, &tiles, config.bingo_samples, &mut rng)?;
+            swaps.push(Score {
+                x: 0,
+                y: 0,
+                horizontal: true,
+                word: String::new(),
+                score: 0,
+                adj_score: -no_move_mean_opp_score.round() as i32,
+                opp_score: no_move_mean_opp_score.round() as i32,
+                opp_std: no_move_std_opp_score,
+                played: board.decode(swapped),
+                exit_flag: ExitFlag::None,
+                opp_score_diff: 0,
+                kind: MoveKind::Swap,
+                leave_score: table.evaluate(keep),
+                risk: 0.0,
+                bingo_bonus,
+                swap_gain: gain,
+            });
+        }
+        rank_scores(&mut swaps, Ranking::Equity);
+        swaps.truncate(MAX_SWAPS);
+        result.extend(swaps);
+    }
+
+    evaluate_leaves(board, rack, &mut result, table)?;
     Ok(result)
 }
 
+/// Return the distinct non-empty subsets of `rack`, as candidate letters to swap.
+pub(crate) fn swap_candidates(rack: Letters) -> Vec<Letters> {
+    let mut letters: Vec<Letter> = rack.into_iter().collect();
+    letters.sort_unstable_by_key(|letter| letter.code());
+    let mut candidates: Vec<Letters> = (1..1_usize << letters.len())
+        .map(|mask| {
+            letters
+                .iter()
+                .enumerate()
+                .filter(|&(i, _)| mask & (1 << i) != 0)
+                .map(|(_, &letter)| letter)
+                .collect()
+        })
+        .collect();
+    candidates.sort_unstable_by_key(|swapped: &Letters| {
+        swapped
+            .iter()
+            .map(|letter| letter.code())
+            .collect::<Vec<_>>()
+    });
+    candidates.dedup();
+    candidates
+}
+
+/// Return the mean and standard deviation of `data`.
+/// If `splits` is not empty, `data` has a value for each split, weighted by its probability.
+fn stats(data: &[i32], splits: &[RackSplit]) -> (f32, f32) {
+    if splits.is_empty() {
+        return (
+            mean(data).unwrap_or(0.0),
+            std_deviation(data).unwrap_or(0.0),
+        );
+    }
+    let weighted = || {
+        data.iter()
+            .zip(splits.iter().map(|split| split.probability))
+    };
+    let mean: f64 = weighted().map(|(&x, p)| x as f64 * p).sum();
+    let variance: f64 = weighted()
+        .map(|(&x, p)| (x as f64 - mean).powi(2) * p)
+        .sum();
+    (mean as f32, variance.sqrt() as f32)
+}
+
+/// z-value of a 95% confidence interval
+pub(crate) const Z_95: f32 = 1.96;
+
 /// From [rust cookbook](https://rust-lang-nursery.github.io/rust-cookbook/science/mathematics/statistics.html)
 fn mean(data: &[i32]) -> Option<f32> {
     let sum = data.iter().sum::<i32>() as f32;
@@ -261,6 +735,273 @@ mod tests {
         "...............",
     ];
 
+    #[test]
+    fn test_swap_candidates() -> Result<()> {
+        let board = Board::new(Language::NL);
+        let candidates: Vec<String> = swap_candidates(board.encode("aba")?)
+            .into_iter()
+            .map(|letters| board.decode(letters))
+            .collect();
+        assert_eq!(candidates, ["a", "aa", "aab", "ab", "b"]);
+        assert_eq!(swap_candidates(board.encode("abcdefg")?).len(), 127);
+        Ok(())
+    }
+
+    #[test]
+    fn test_find_swaps() -> Result<()> {
+        let mut board = Board::new(Language::NL)
+            .with_wordlist_from_words(&["zon", "zoen", "gen", "nog"])?
+            .with_state_from_strings(TEST_STATE)?;
+        let rack: Letters = board.encode("gnnnoqz")?;
+        let scores = find_best_scores(&mut board, rack, 10)?;
+        assert!(scores.iter().all(|s| s.kind != MoveKind::Swap));
+        let config = SearchConfig {
+            samples: 10,
+            ..SearchConfig::default()
+        };
+        let scores = find_best_scores_with(&mut board, rack, &config)?;
+        let swaps: Vec<_> = scores.iter().filter(|s| s.kind == MoveKind::Swap).collect();
+        assert!(!swaps.is_empty() && swaps.len() <= MAX_SWAPS);
+        // the swaps are selected by equity, with the bingo bonus included
+        assert!(swaps
+            .windows(2)
+            .all(|w| w[0].value(Ranking::Equity) >= w[1].value(Ranking::Equity)));
+        for s in swaps {
+            assert_eq!(s.score, 0);
+            // a swap is scored like a word that scores no points
+            assert_eq!(s.adj_score, -s.opp_score);
+            let swapped: Letters = board.encode(&s.played)?;
+            assert!(rack.remove_letters(swapped).is_ok());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_swap_gain_ranks_swaps() -> Result<()> {
+        let mut board = Board::new(Language::NL)
+            .with_wordlist_from_words(&["zon", "zoen", "gen", "nog"])?
+            .with_state_from_strings(TEST_STATE)?;
+        let rack: Letters = board.encode("gnnnoqz")?;
+        // without leave values and bingo estimates, the swaps differ only by the sampled refills
+        let mut table = LeaveTable::new(Language::NL);
+        table.letters.iter_mut().for_each(|value| *value = 0.0);
+        table.blank = 0.0;
+        table.duplicate = 0.0;
+        table.balance = 0.0;
+        table.q_without_u = 0.0;
+        let config = SearchConfig {
+            samples: 10,
+            bingo_samples: 0,
+            leave: Some(table),
+            ..SearchConfig::default()
+        };
+        let scores = find_best_scores_with(&mut board, rack, &config)?;
+        let swaps: Vec<_> = scores.iter().filter(|s| s.kind == MoveKind::Swap).collect();
+        assert!(swaps.len() > 1);
+        assert!(swaps.windows(2).all(|w| w[0].swap_gain >= w[1].swap_gain));
+        // without the sampled refills another swap would rank first
+        let mut without_gain: Vec<Score> = swaps
+            .iter()
+            .map(|&s| Score {
+                swap_gain: 0.0,
+                ..s.clone()
+            })
+            .collect();
+        rank_scores(&mut without_gain, Ranking::Equity);
+        assert_ne!(without_gain[0].played, swaps[0].played);
+        Ok(())
+    }
+
+    #[test]
+    fn test_search_keeps_history() -> Result<()> {
+        for &limit in &[32, 1, 0] {
+            let mut board = Board::new(Language::NL)
+                .with_wordlist_from_words(&["zon", "zoen", "gen", "nog", "no"])?
+                .with_history_limit(limit);
+            let rack: Letters = board.encode("gnnnoqz")?;
+            assert!(!find_best_scores(&mut board, rack, 10)?.is_empty());
+            assert!(board.is_empty());
+            assert!(board.history().is_empty());
+            assert!(board.redo().is_none());
+            if limit == 0 {
+                continue;
+            }
+
+            // the moves of the user can still be undone and redone after a search
+            board.play_word("zon", 7, 7, true, true)?;
+            let state = board.horizontal();
+            find_best_scores(&mut board, rack, 10)?;
+            assert_eq!(board.horizontal(), state);
+            assert_eq!(board.history().len(), 1);
+            board.undo();
+            find_best_scores(&mut board, rack, 10)?;
+            assert!(board.is_empty());
+            assert!(board.redo().is_some());
+            assert_eq!(board.horizontal(), state);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_search_config() -> Result<()> {
+        let mut board = Board::new(Language::NL)
+            .with_wordlist_from_words(&["zon", "zoen", "gen", "nog", "og", "on"])?
+            .with_state_from_strings(TEST_STATE)?;
+        let rack: Letters = board.encode("gnnnoqz")?;
+        let config = SearchConfig {
+            candidates: 2,
+            samples: 5,
+            swaps: false,
+            threads: Some(2),
+            ..SearchConfig::default()
+        };
+        let scores = find_best_scores_with(&mut board, rack, &config)?;
+        assert!(!scores.is_empty() && scores.len() <= 2);
+        assert!(scores.iter().all(|s| s.kind == MoveKind::Word));
+
+        // the same seed gives the same results
+        let again = find_best_scores_with(&mut board, rack, &config)?;
+        let values = |scores: &[Score]| -> Vec<(String, i32)> {
+            scores
+                .iter()
+                .map(|s| (s.word.clone(), s.adj_score))
+                .collect()
+        };
+        assert_eq!(values(&scores), values(&again));
+
+        // with no time left only one word is evaluated, and no swaps
+        let config = SearchConfig {
+            time_budget: Some(std::time::Duration::from_secs(0)),
+            seed: None,
+            ..SearchConfig::default()
+        };
+        let scores = find_best_scores_with(&mut board, rack, &config)?;
+        assert_eq!(scores.len(), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_thread_count_independence() -> Result<()> {
+        let mut board = Board::new(Language::NL)
+            .with_wordlist_from_words(&["zon", "zoen", "gen", "nog", "og", "on", "zo", "no"])?
+            .with_state_from_strings(TEST_STATE)?;
+        let rack: Letters = board.encode("gnnnoqz")?;
+        let mut results = Vec::new();
+        for &threads in &[1, 2, 4] {
+            let config = SearchConfig {
+                samples: 20,
+                threads: Some(threads),
+                ..SearchConfig::default()
+            };
+            let mut scores = find_best_scores_with(&mut board, rack, &config)?;
+            rank_scores(&mut scores, Ranking::Equity);
+            let values: Vec<_> = scores
+                .iter()
+                .map(|s| {
+                    (
+                        (s.x, s.y, s.horizontal, s.word.clone(), s.played.clone()),
+                        (s.adj_score, s.opp_std.to_bits(), s.bingo_bonus.to_bits()),
+                    )
+                })
+                .collect();
+            results.push(values);
+        }
+        assert!(!results[0].is_empty());
+        assert_eq!(results[0], results[1]);
+        assert_eq!(results[0], results[2]);
+        Ok(())
+    }
+
+    /// Return a board state with all tiles of `board` on it, except for `rack` and `unseen`.
+    /// The tiles are placed on the odd rows, so the even rows are free.
+    fn fill_board(board: &Board, rack: &str, unseen: &str) -> Result<Vec<String>> {
+        let mut bag = TileBag::from(board.tileset());
+        for letters in &[rack, unseen] {
+            let letters: Letters = board.encode(letters)?;
+            let codes: Vec<Code> = letters
+                .into_iter()
+                .map(|letter: Letter| letter.code())
+                .collect();
+            bag = bag - TileBag::from(&codes);
+        }
+        let mut codes: Vec<Code> = bag.iter().cloned().collect();
+        codes.sort_unstable();
+        let labels: Vec<String> = codes
+            .into_iter()
+            .map(|code| match code {
+                BLANK => String::from("E"), // a blank played as "e"
+                _ => String::from(board.tileset().label(code)),
+            })
+            .collect();
+        let mut rows = vec![String::from("..............."); 15];
+        for (i, chunk) in labels.chunks(15).enumerate() {
+            rows[2 * i + 1] = format!("{:.<15}", chunk.concat());
+        }
+        Ok(rows)
+    }
+
+    #[test]
+    fn test_pre_endgame() -> Result<()> {
+        let mut board = Board::new(Language::EN).with_wordlist_from_words(&["ab", "ba", "abc"])?;
+        let state = fill_board(&board, "abcdefg", "aaabcdefgh")?;
+        board.set_state_from_strings(&state)?;
+        let rack: Letters = board.encode("abcdefg")?;
+        let remaining = remaining_tiles(&TileBag::from(board.tileset()), &board, rack);
+        assert_eq!(remaining.len(), 10);
+        let config = SearchConfig {
+            samples: 10,
+            swaps: false,
+            bingo_samples: 0,
+            ..SearchConfig::default()
+        };
+        let scores = find_best_scores_with(&mut board, rack, &config)?;
+        assert!(scores.iter().any(|s| s.kind == MoveKind::Word));
+        let pass = scores.iter().find(|s| s.kind == MoveKind::Pass).unwrap();
+        // the opponent can play "ab" or "ba" with probability 1
+        assert!(pass.opp_score > 0);
+        assert!(scores.iter().all(|s| s.kind != MoveKind::Swap));
+        // the old interface returns only words
+        let words = find_best_scores(&mut board, rack, 10)?;
+        assert!(!words.is_empty());
+        assert!(words.iter().all(|s| s.kind == MoveKind::Word));
+
+        // without words we can still pass
+        let state = fill_board(&board, "cdefghi", "aaabcdefgh")?;
+        board.set_state_from_strings(&state)?;
+        let rack: Letters = board.encode("cdefghi")?;
+        let scores = find_best_scores_with(&mut board, rack, &config)?;
+        assert_eq!(scores.len(), 1);
+        assert_eq!(scores[0].kind, MoveKind::Pass);
+        assert!(find_best_scores(&mut board, rack, 10)?.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_rank_scores() -> Result<()> {
+        let mut board = Board::new(Language::NL)
+            .with_wordlist_from_words(&["zon", "zoen", "gen", "nog", "zee"])?
+            .with_state_from_strings(TEST_STATE)?;
+        let rack: Letters = board.encode("eegnnoz")?;
+        let mut scores = find_best_scores(&mut board, rack, 5)?;
+        let table = LeaveTable::new(Language::NL);
+        for s in &scores {
+            let leave = rack.remove_letters(board.encode(&s.played)?).unwrap();
+            assert_eq!(s.leave_score, table.evaluate(leave));
+        }
+        rank_scores(&mut scores, Ranking::Equity);
+        for pair in scores.windows(2) {
+            assert!(pair[0].value(Ranking::Equity) >= pair[1].value(Ranking::Equity));
+        }
+
+        // the played letters must be on the rack
+        let other: Letters = board.encode("aaaaaaa")?;
+        assert!(matches!(
+            evaluate_leaves(&board, other, &mut scores, &table),
+            Err(Error::LettersNotInRack(_))
+        ));
+        Ok(())
+    }
+
     #[test]
     fn test_remaining_letters() -> Result<()> {
         let board = Board::new(Language::NL)
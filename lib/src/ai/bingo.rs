@@ -0,0 +1,100 @@
+use super::draw_random_tiles;
+use crate::game::RACK_SIZE;
+use crate::{Board, Code, Error, Letter, Letters, List, Row, Word};
+use rand::Rng;
+use std::convert::TryFrom;
+
+/// Check if a word can be played on `board` with all letters of `rack`.
+///
+/// Words are only tried from the start positions given by
+/// [`start_indices`](crate::Wordlist::start_indices), the anchors of the board.
+pub fn has_bingo(board: &Board, rack: Letters) -> bool {
+    let wordlist = board.wordlist();
+    [false, true].iter().any(|&horizontal| {
+        let state = if horizontal {
+            board.horizontal()
+        } else {
+            board.vertical()
+        };
+        state
+            .iter()
+            .zip(board.rowdata(horizontal))
+            .any(|(row, rowdata)| {
+                wordlist
+                    .words(row, rowdata, &rack, None)
+                    .any(|(pos, word)| placed_tiles(row, pos, &word) == rack.len())
+            })
+    })
+}
+
+/// Return the number of tiles that `word` at `pos` places in `row`.
+fn placed_tiles(row: &Row, pos: usize, word: &Word) -> usize {
+    (pos..pos + word.len())
+        .filter(|&i| row[i].is_empty())
+        .count()
+}
+
+/// Estimate the probability that `leave`, refilled to a full rack from `tiles`, has a bingo on `board`.
+///
+/// The refills are `samples` random draws from `tiles`, the tiles we have not seen.
+/// The probability is 0 if there are not enough tiles for a refill.
+/// ## Errors
+/// If a tile is not a valid letter.
+pub fn bingo_probability<R: Rng>(
+    board: &Board,
+    leave: Letters,
+    tiles: &[Code],
+    samples: usize,
+    rng: &mut R,
+) -> Result<f32, Error> {
+    let refill = RACK_SIZE.saturating_sub(leave.len());
+    if samples == 0 || refill == 0 || refill > tiles.len() {
+        return Ok(0.0);
+    }
+    let mut bingos = 0;
+    for _ in 0..samples {
+        let mut rack = leave;
+        for code in draw_random_tiles(tiles, refill, rng) {
+            rack.push(Letter::try_from(code)?);
+        }
+        if has_bingo(board, rack) {
+            bingos += 1;
+        }
+    }
+    Ok(bingos as f32 / samples as f32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Item, Language};
+    use rand::{rngs::StdRng, SeedableRng};
+
+    type Result<T> = std::result::Result<T, Error>;
+
+    #[test]
+    fn test_bingo_probability() -> Result<()> {
+        let mut board =
+            Board::new(Language::NL).with_wordlist_from_words(&["rust", "lusters", "trusters"])?;
+        assert!(has_bingo(&board, board.encode("lusters")?));
+        assert!(!has_bingo(&board, board.encode("lustera")?));
+
+        // after "rust", the 8 letter word "trusters" can be played through the "r" with 7 tiles,
+        // but "lusters" can only be played through the "u" with 6 tiles
+        board.play_word("rust", 7, 7, true, true)?;
+        assert!(has_bingo(&board, board.encode("tusters")?));
+        assert!(!has_bingo(&board, board.encode("lusters")?));
+
+        let mut rng = StdRng::seed_from_u64(1);
+        let leave: Letters = board.encode("tuster")?;
+        let tiles: Vec<Code> = board
+            .encode::<Letter>("sssa")?
+            .iter()
+            .map(|l| l.code())
+            .collect();
+        let p = bingo_probability(&board, leave, &tiles, 100, &mut rng)?;
+        assert!(p > 0.5 && p < 1.0);
+        assert_eq!(bingo_probability(&board, leave, &[], 100, &mut rng)?, 0.0);
+        Ok(())
+    }
+}
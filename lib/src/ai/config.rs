@@ -0,0 +1,65 @@
+use super::{LeaveTable, OpponentModel};
+use std::time::Duration;
+
+/// Parameters for [`find_best_scores_with`](crate::find_best_scores_with).
+///
+/// ## Examples
+/// ```
+/// # use wordfeud_solver::SearchConfig;
+/// use std::time::Duration;
+///
+/// let config = SearchConfig {
+///     samples: 100,
+///     time_budget: Some(Duration::from_millis(500)),
+///     ..SearchConfig::default()
+/// };
+/// ```
+#[derive(Debug, Clone)]
+pub struct SearchConfig {
+    /// seed for the random generator, or `None` to seed it from system entropy
+    pub seed: Option<u64>,
+    /// number of our best words to evaluate (all words are evaluated in the endgame)
+    pub candidates: usize,
+    /// number of random opponent racks to evaluate each word with
+    pub samples: usize,
+    /// number of tiles on a random opponent rack
+    pub rack_size: usize,
+    /// stop evaluating words after this time. At least one word is evaluated
+    pub time_budget: Option<Duration>,
+    /// evaluate swaps
+    pub swaps: bool,
+    /// number of threads to calculate opponent scores, or `None` for the `rayon` default.
+    /// Only used with the `rayon` feature
+    pub threads: Option<usize>,
+    /// table to evaluate leaves with, or `None` for the default table for the language of the board
+    pub leave: Option<LeaveTable>,
+    /// model to draw the random opponent racks from, or `None` to draw them uniformly from the unseen tiles
+    pub opponent: Option<OpponentModel>,
+    /// number of random refills of our leave to estimate the probability of a bingo next turn,
+    /// or 0 to skip the estimate
+    pub bingo_samples: usize,
+    /// maximum number of opponent racks to evaluate each word with in the pre-endgame,
+    /// or `None` to evaluate all of them. Setting it makes the result approximate:
+    /// only the most probable racks are evaluated, and their probabilities are scaled to sum to 1
+    pub max_rack_splits: Option<usize>,
+}
+
+/// The parameters used by [`find_best_scores`](crate::find_best_scores), with 50 samples,
+/// plus swaps and bingo estimates.
+impl Default for SearchConfig {
+    fn default() -> Self {
+        SearchConfig {
+            seed: Some(123),
+            candidates: 20,
+            samples: 50,
+            rack_size: 7,
+            time_budget: None,
+            swaps: true,
+            threads: None,
+            leave: None,
+            opponent: None,
+            bingo_samples: 10,
+            max_rack_splits: None,
+        }
+    }
+}
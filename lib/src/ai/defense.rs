@@ -0,0 +1,215 @@
+use super::{MoveKind, Score};
+use crate::game::RACK_SIZE;
+use crate::grid::Cell::WordBonus;
+use crate::wordlist::{LetterSet, RowData};
+use crate::{Board, Error, Word};
+
+const N: usize = 15;
+
+/// Risk in points of an exposed triple word square
+const TRIPLE_WORD_RISK: f32 = 8.0;
+/// Risk in points of a lane to a triple word square
+const TRIPLE_WORD_LANE_RISK: f32 = 2.0;
+/// Risk in points of a hook spot
+const HOOK_RISK: f32 = 0.25;
+/// Risk in points of a bingo lane
+const BINGO_LANE_RISK: f32 = 3.0;
+
+/// Static measure of the opportunities a board gives to the next player.
+///
+/// Unlike the opponent scores of [`find_best_scores`](crate::find_best_scores), the exposure
+/// is calculated without sampling racks, from the [grid](Board::grid) and the
+/// [rowdata](Board::rowdata) of the board. This makes it fast enough to use when sampling is too slow.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Exposure {
+    /// number of empty triple word squares where a tile can be placed
+    pub triple_words: usize,
+    /// number of triple word squares and directions, for which a word can reach the square
+    /// from a tile or anchor
+    pub triple_word_lanes: usize,
+    /// number of empty squares and directions where a tile extends a word on the board
+    pub hook_spots: usize,
+    /// number of rows and columns with room for a word with all letters of a rack
+    pub bingo_lanes: usize,
+}
+
+impl Exposure {
+    /// Measure the exposure of `board`.
+    pub fn new(board: &Board) -> Exposure {
+        let rowdata: [Vec<&RowData>; 2] = [
+            board.rowdata(false).collect(),
+            board.rowdata(true).collect(),
+        ];
+        let grid = board.grid();
+        // (x, y) of square `j` in row `i` of direction `horizontal`
+        let square =
+            |horizontal: bool, i: usize, j: usize| if horizontal { (j, i) } else { (i, j) };
+        let data = |horizontal: bool, x: usize, y: usize| -> (LetterSet, bool) {
+            if horizontal {
+                rowdata[1][y][x]
+            } else {
+                rowdata[0][x][y]
+            }
+        };
+        let is_free = |horizontal: bool, x: usize, y: usize| {
+            !board.is_occupied(x, y) && !data(horizontal, x, y).0.is_empty()
+        };
+        // a tile can be placed on an anchor, connected to the tiles on the board
+        let is_anchor = |x: usize, y: usize| {
+            !board.is_occupied(x, y)
+                && [false, true].iter().any(|&h| {
+                    let (letters, connected) = data(h, x, y);
+                    connected && !letters.is_empty()
+                })
+        };
+
+        let mut exposure = Exposure::default();
+        // an empty board has only the start square, which is not a hook
+        let has_hooks = !board.is_empty();
+        for y in 0..N {
+            for x in 0..N {
+                if board.is_occupied(x, y) {
+                    continue;
+                }
+                for &h in &[false, true] {
+                    let (letters, connected) = data(h, x, y);
+                    if has_hooks && connected && !letters.is_empty() {
+                        exposure.hook_spots += 1;
+                    }
+                }
+                if grid[y][x] != WordBonus(3) {
+                    continue;
+                }
+                if is_anchor(x, y) {
+                    exposure.triple_words += 1;
+                }
+                for &h in &[false, true] {
+                    let (i, j) = if h { (y, x) } else { (x, y) };
+                    // walk from the square to a tile or anchor, over at most a rack of free squares
+                    let reachable = |step: isize| {
+                        let squares = (0..=RACK_SIZE as isize)
+                            .map(|d| j as isize + step * d)
+                            .take_while(|&k| (0..N as isize).contains(&k))
+                            .map(|k| square(h, i, k as usize));
+                        for (d, (x, y)) in squares.enumerate() {
+                            if board.is_occupied(x, y) {
+                                return true;
+                            }
+                            if d == RACK_SIZE || !is_free(h, x, y) {
+                                return false;
+                            }
+                            if is_anchor(x, y) {
+                                return true;
+                            }
+                        }
+                        false
+                    };
+                    if reachable(-1) || reachable(1) {
+                        exposure.triple_word_lanes += 1;
+                    }
+                }
+            }
+        }
+
+        for &h in &[false, true] {
+            for i in 0..N {
+                let free: Vec<bool> = (0..N)
+                    .map(|j| {
+                        let (x, y) = square(h, i, j);
+                        is_free(h, x, y)
+                    })
+                    .collect();
+                let has_lane = (0..=N - RACK_SIZE).any(|start| {
+                    let window = start..start + RACK_SIZE;
+                    free[window.clone()].iter().all(|&f| f)
+                        && window.into_iter().any(|j| {
+                            let (x, y) = square(h, i, j);
+                            is_anchor(x, y)
+                        })
+                });
+                if has_lane {
+                    exposure.bingo_lanes += 1;
+                }
+            }
+        }
+        exposure
+    }
+
+    /// Return the risk in points: the weighted sum of the exposure measures.
+    pub fn risk(&self) -> f32 {
+        self.triple_words as f32 * TRIPLE_WORD_RISK
+            + self.triple_word_lanes as f32 * TRIPLE_WORD_LANE_RISK
+            + self.hook_spots as f32 * HOOK_RISK
+            + self.bingo_lanes as f32 * BINGO_LANE_RISK
+    }
+}
+
+/// Set the `risk` of each of `scores` to the increase of the [`Exposure`] risk of `board` by the move.
+/// Swaps and passes do not change the board and have no risk.
+///
+/// The scores can come from [`find_best_scores`](crate::find_best_scores), or without sampling
+/// from [calc_all_word_scores](Board::calc_all_word_scores) and [`word_score`](crate::word_score).
+/// ## Errors
+/// If a word can not be encoded or played on the board.
+pub fn evaluate_risks(board: &mut Board, scores: &mut [Score]) -> Result<(), Error> {
+    let before = Exposure::new(board).risk();
+    for s in scores {
+        if s.kind != MoveKind::Word {
+            s.risk = 0.0;
+            continue;
+        }
+        let word: Word = board.encode(&s.word)?;
+        let placement = board.word_placement(word, s.x, s.y, s.horizontal);
+        let (_, record) = board.place(&placement)?;
+        s.risk = Exposure::new(board).risk() - before;
+        board.unplace(&record);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Language;
+
+    type Result<T> = std::result::Result<T, Error>;
+
+    #[test]
+    fn test_exposure() -> Result<()> {
+        let mut board = Board::new(Language::NL).with_wordlist_from_words(&["rust", "rusts"])?;
+        let empty = Exposure::new(&board);
+        assert_eq!(empty.triple_words, 0);
+        assert_eq!(empty.triple_word_lanes, 0);
+        assert_eq!(empty.hook_spots, 0);
+
+        // "rust" from (7,4) to (7,7) gives a lane to the triple word squares at (0,4) and (14,4),
+        // and can be extended to "rusts"
+        board.play_word("rust", 7, 4, false, true)?;
+        let exposure = Exposure::new(&board);
+        assert_eq!(exposure.triple_word_lanes, 2);
+        assert!(exposure.hook_spots > 0);
+        assert!(exposure.bingo_lanes > 0);
+        assert!(exposure.risk() > empty.risk());
+        Ok(())
+    }
+
+    #[test]
+    fn test_evaluate_risks() -> Result<()> {
+        let mut board = Board::new(Language::NL)
+            .with_wordlist_from_words(&["rust", "rusts"])?
+            .with_history_limit(0);
+        let mut scores: Vec<Score> = board
+            .calc_all_word_scores("rustxyz")?
+            .iter()
+            .map(|s| crate::word_score(&board, s))
+            .collect();
+        let state = board.horizontal();
+        evaluate_risks(&mut board, &mut scores)?;
+        assert_eq!(board.horizontal(), state);
+        assert!(board.redo().is_none());
+        assert!(scores
+            .iter()
+            .any(|s| s.kind == MoveKind::Word && s.risk != 0.0));
+        Ok(())
+    }
+}
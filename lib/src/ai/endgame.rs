@@ -0,0 +1,289 @@
+use super::tiles_score;
+use crate::game::MAX_SCORELESS_TURNS;
+use crate::{Board, Error, Letters, List, Score};
+use std::time::{Duration, Instant};
+
+/// Limits for [`solve_endgame`].
+///
+/// The search has no transposition table, so the number of positions grows exponentially with the depth:
+/// every position has all words that can be played with the rack, plus a pass.
+/// With full racks an exact search can take hours, so the default searches at most 8 moves ahead,
+/// and stops after 10 seconds.
+#[derive(Debug, Clone, Copy)]
+pub struct EndgameLimits {
+    /// maximum number of moves (of both players) to search ahead
+    pub max_depth: usize,
+    /// stop searching after this time
+    pub time_limit: Option<Duration>,
+}
+
+impl Default for EndgameLimits {
+    fn default() -> Self {
+        EndgameLimits {
+            max_depth: 8,
+            time_limit: Some(Duration::from_secs(10)),
+        }
+    }
+}
+
+/// A move in the principal variation of an [`EndgameResult`]
+#[derive(Debug, Clone, Copy)]
+pub enum EndgameMove {
+    /// Play a word
+    Word(Score),
+    /// Pass the turn
+    Pass,
+}
+
+/// Result of [`solve_endgame`]
+#[derive(Debug, Clone)]
+pub struct EndgameResult {
+    /// the points we score minus the points the opponent scores from now on,
+    /// including the rack adjustment at the end of the game
+    pub spread: i32,
+    /// the best moves for both players, starting with our move
+    pub moves: Vec<EndgameMove>,
+    /// true if the search reached the end of the game in every line,
+    /// false if it was cut off by the depth or time limit
+    pub exact: bool,
+    /// number of positions searched
+    pub nodes: usize,
+}
+
+/// Find the best sequence of moves when the bag is empty.
+///
+/// With an empty bag the letters of the opponent are known: they are the
+/// [remaining tiles](crate::remaining_tiles). The game is then a game with perfect information,
+/// and we can search all moves of both players with minimax and alpha-beta pruning.
+///
+/// The game ends when a player has used all letters, or when both players passed twice.
+/// A player that uses all letters gets the value of the letters of the opponent,
+/// and the opponent loses that value. When both players passed, each loses the value of their letters.
+///
+/// When the search is cut off by `limits`, the position is evaluated as if the game ended
+/// by passing.
+/// ## Errors
+/// If the word scores can not be calculated, see [calc_all_word_scores](crate::Board::calc_all_word_scores).
+pub fn solve_endgame(
+    board: &mut Board,
+    rack: Letters,
+    opp_rack: Letters,
+    limits: EndgameLimits,
+) -> Result<EndgameResult, Error> {
+    let mut search = Search {
+        board,
+        limits,
+        start: Instant::now(),
+        nodes: 0,
+        exact: true,
+    };
+    let (spread, moves) =
+        search.negamax([rack, opp_rack], 0, limits.max_depth, -i32::MAX, i32::MAX)?;
+    Ok(EndgameResult {
+        spread,
+        moves,
+        exact: search.exact,
+        nodes: search.nodes,
+    })
+}
+
+struct Search<'b, 'a> {
+    board: &'b mut Board<'a>,
+    limits: EndgameLimits,
+    start: Instant,
+    nodes: usize,
+    exact: bool,
+}
+
+impl<'b, 'a> Search<'b, 'a> {
+    fn timed_out(&self) -> bool {
+        self.limits
+            .time_limit
+            .is_some_and(|limit| self.start.elapsed() >= limit)
+    }
+
+    /// Returns the spread for the player to move with `racks[0]`, and the best line of moves.
+    fn negamax(
+        &mut self,
+        racks: [Letters; 2],
+        passes: usize,
+        depth: usize,
+        mut alpha: i32,
+        beta: i32,
+    ) -> Result<(i32, Vec<EndgameMove>), Error> {
+        self.nodes += 1;
+        let [rack, opp_rack] = racks;
+        let rack_score = tiles_score(&rack, self.board.tileset());
+        let opp_rack_score = tiles_score(&opp_rack, self.board.tileset());
+        if depth == 0 || self.timed_out() {
+            self.exact = false;
+            return Ok((opp_rack_score - rack_score, Vec::new()));
+        }
+
+        let mut words = self.board.calc_all_word_scores(rack)?;
+        words.sort_by(|a, b| b.cmp(a));
+        let moves = words
+            .into_iter()
+            .map(EndgameMove::Word)
+            .chain(std::iter::once(EndgameMove::Pass));
+
+        let mut best = (-i32::MAX, Vec::new());
+        for mv in moves {
+            let (value, mut line) = match mv {
+                EndgameMove::Word(s) => {
+                    let placement = self.board.word_placement(s.word, s.x, s.y, s.horizontal);
+                    let (play, record) = self.board.place(&placement)?;
+                    let left = rack.remove_letters(play.used).unwrap_or_default();
+                    let res = if left.is_empty() {
+                        // we used all letters: the game is over
+                        Ok((s.score as i32 + 2 * opp_rack_score, Vec::new()))
+                    } else {
+                        self.negamax([opp_rack, left], 0, depth - 1, -beta, -alpha)
+                            .map(|(value, line)| (s.score as i32 - value, line))
+                    };
+                    self.board.unplace(&record);
+                    res?
+                }
+                EndgameMove::Pass => {
+                    if passes + 1 >= 2 * MAX_SCORELESS_TURNS {
+                        // both players passed twice: the game is over
+                        (opp_rack_score - rack_score, Vec::new())
+                    } else {
+                        let (value, line) =
+                            self.negamax([opp_rack, rack], passes + 1, depth - 1, -beta, -alpha)?;
+                        (-value, line)
+                    }
+                }
+            };
+            if value > best.0 {
+                line.insert(0, mv);
+                best = (value, line);
+            }
+            alpha = alpha.max(value);
+            if alpha >= beta {
+                break;
+            }
+        }
+        Ok(best)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Language;
+
+    type Result<T> = std::result::Result<T, Error>;
+
+    fn points(board: &Board, letters: &str) -> i32 {
+        tiles_score(&board.encode(letters).unwrap(), board.tileset())
+    }
+
+    #[test]
+    fn test_exit() -> Result<()> {
+        let mut board = Board::new(Language::EN).with_wordlist_from_words(&["ab", "ba"])?;
+        let (rack, opp_rack) = (board.encode("ab")?, board.encode("q")?);
+        let res = solve_endgame(&mut board, rack, opp_rack, EndgameLimits::default())?;
+        let words = board.calc_all_word_scores("ab")?;
+        let score = words.iter().map(|s| s.score as i32).max().unwrap();
+        assert!(res.exact);
+        assert_eq!(res.spread, score + 2 * points(&board, "q"));
+        assert!(matches!(res.moves[..], [EndgameMove::Word(s)] if s.score as i32 == score));
+        assert!(board.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_passes() -> Result<()> {
+        let mut board = Board::new(Language::EN).with_wordlist_from_words(&["ab"])?;
+        let (rack, opp_rack) = (board.encode("xz")?, board.encode("q")?);
+        let res = solve_endgame(&mut board, rack, opp_rack, EndgameLimits::default())?;
+        assert!(res.exact);
+        assert_eq!(res.spread, points(&board, "q") - points(&board, "xz"));
+        assert_eq!(res.moves.len(), 4);
+        assert!(res.moves.iter().all(|mv| matches!(mv, EndgameMove::Pass)));
+        Ok(())
+    }
+
+    #[test]
+    fn test_principal_variation() -> Result<()> {
+        let words = &[
+            "ab", "ba", "bad", "dab", "cab", "ad", "da", "ace", "bead", "be",
+        ];
+        let mut board = Board::new(Language::EN)
+            .with_wordlist_from_words(words)?
+            .with_state_from_strings(&[
+                "...............",
+                "...............",
+                "...............",
+                "...............",
+                "...............",
+                "...............",
+                "...............",
+                ".......bad.....",
+                "...............",
+                "...............",
+                "...............",
+                "...............",
+                "...............",
+                "...............",
+                "...............",
+            ])?;
+        let state = board.horizontal();
+        let rack: Letters = board.encode("acbe")?;
+        let opp_rack: Letters = board.encode("dab")?;
+        // the default depth is not enough to reach the end of the game in every line
+        let limits = EndgameLimits {
+            max_depth: usize::MAX,
+            time_limit: None,
+        };
+        let res = solve_endgame(&mut board, rack, opp_rack, limits)?;
+        assert_eq!(board.horizontal(), state);
+        assert!(res.exact);
+        assert!(res.moves.len() > 1);
+
+        // replay the principal variation, and check that it gives the spread
+        let mut racks = [rack, opp_rack];
+        let mut spread = 0;
+        let mut sign = 1;
+        for mv in &res.moves {
+            if let EndgameMove::Word(s) = mv {
+                let placement = board.word_placement(s.word, s.x, s.y, s.horizontal);
+                let play = board.play(&placement)?;
+                racks[0] = racks[0].remove_letters(play.used).unwrap();
+                spread += sign * play.score as i32;
+            }
+            racks.swap(0, 1);
+            sign = -sign;
+        }
+        // the player to move is racks[0], the player that moved last is racks[1]
+        let last = tiles_score(&racks[1], board.tileset());
+        let next = tiles_score(&racks[0], board.tileset());
+        let end = if last == 0 { 2 * next } else { next - last };
+        spread += -sign * end;
+        assert_eq!(spread, res.spread);
+
+        // a depth limit gives an estimate
+        board.set_state(&state);
+        let limits = EndgameLimits {
+            max_depth: 1,
+            time_limit: None,
+        };
+        let res = solve_endgame(&mut board, rack, opp_rack, limits)?;
+        assert!(!res.exact);
+        assert_eq!(res.moves.len(), 1);
+
+        // the depth does not depend on the history of the board
+        board.set_history_limit(0);
+        let limits = EndgameLimits {
+            max_depth: usize::MAX,
+            time_limit: None,
+        };
+        let res = solve_endgame(&mut board, rack, opp_rack, limits)?;
+        assert!(res.exact);
+        assert_eq!(spread, res.spread);
+        assert_eq!(board.horizontal(), state);
+        assert!(board.history().is_empty());
+        Ok(())
+    }
+}
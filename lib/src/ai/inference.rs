@@ -0,0 +1,210 @@
+use super::{remaining_tiles, LeaveTable};
+use crate::game::RACK_SIZE;
+use crate::tilebag::TileBag;
+use crate::{Board, Code, Error, Item, Letter, Letters, List, Move};
+use rand::distributions::{Distribution, WeightedIndex};
+use rand::{seq::SliceRandom, Rng};
+use std::convert::TryFrom;
+
+/// Temperature of the opponent move choice, in points:
+/// a move that scores 10 points less than the best move is `e` times less likely.
+const TEMPERATURE: f64 = 10.0;
+
+/// Possible opponent leaves, weighted by the evidence of the last opponent move.
+///
+/// Without information, the opponent rack is a uniform draw from the unseen tiles.
+/// But the last move of the opponent tells what they probably kept:
+/// - after a word, leaves with which they could have played a much better word are unlikely,
+/// - after a swap, the letters they kept are probably a good leave, see [`LeaveTable`],
+/// - after a pass, racks with a good word are unlikely.
+///
+/// The opponent is modelled to choose a move with a probability that decreases exponentially
+/// with the points it scores less than the best move. The leaves are drawn from the unseen tiles,
+/// and weighted with the probability of the observed move, using [`Board::sample_scores`].
+///
+/// Use the model with [`SearchConfig::opponent`](crate::SearchConfig::opponent) to sample opponent racks
+/// in [`find_best_scores_with`](crate::find_best_scores_with).
+#[derive(Debug, Clone)]
+pub struct OpponentModel {
+    leaves: Vec<(Letters, f64)>,
+    unseen: Vec<Code>,
+}
+
+impl OpponentModel {
+    /// Infer the opponent leave from `last_move`, played by the opponent on `board`,
+    /// with our letters in `rack`. `nleaves` possible leaves are drawn with `rng`.
+    /// The model is used with the board after the move, with the same `rack`.
+    /// ## Errors
+    /// If the move can not be played on `board`, or the scores can not be calculated.
+    pub fn infer<R: Rng>(
+        board: &Board,
+        last_move: &Move,
+        rack: Letters,
+        nleaves: usize,
+        rng: &mut R,
+    ) -> Result<OpponentModel, Error> {
+        let mut after = board.clone();
+        let (kept, observed) = match last_move {
+            Move::Tiles(placement) => {
+                let play = after.play(placement)?;
+                (RACK_SIZE.saturating_sub(play.used.len()), Some(play))
+            }
+            Move::Swap(letters) => (RACK_SIZE.saturating_sub(letters.len()), None),
+            Move::Pass => (RACK_SIZE, None),
+        };
+        let full_bag = TileBag::from(after.tileset());
+        let unseen = remaining_tiles(&full_bag, &after, rack).sorted();
+        let kept = kept.min(unseen.len());
+
+        let mut pool = unseen.clone();
+        let leaves: Vec<Letters> = (0..nleaves)
+            .map(|_| {
+                let (drawn, _) = pool.partial_shuffle(rng, kept);
+                drawn.iter().map(|&code| Letter::try_from(code)).collect()
+            })
+            .collect::<Result<_, _>>()?;
+
+        // log-likelihood of the observed move for each leave
+        let log_weights: Vec<f64> = match (last_move, observed) {
+            (Move::Tiles(_), Some(play)) => {
+                let racks: Vec<Letters> = leaves
+                    .iter()
+                    .map(|&leave| {
+                        let mut letters = leave;
+                        for letter in play.used {
+                            letters.push(letter);
+                        }
+                        letters
+                    })
+                    .collect();
+                board
+                    .sample_scores(&racks, 0, false)?
+                    .iter()
+                    .map(|&(best, _)| -f64::from(best.saturating_sub(play.score)) / TEMPERATURE)
+                    .collect()
+            }
+            (Move::Swap(_), _) => {
+                let table = LeaveTable::new(board.tileset().language.clone());
+                leaves
+                    .iter()
+                    .map(|&leave| table.evaluate(leave) as f64 / TEMPERATURE)
+                    .collect()
+            }
+            _ => board
+                .sample_scores(&leaves, 0, false)?
+                .iter()
+                .map(|&(best, _)| -f64::from(best) / TEMPERATURE)
+                .collect(),
+        };
+        let max = log_weights.iter().cloned().fold(f64::MIN, f64::max);
+        let total: f64 = log_weights.iter().map(|w| (w - max).exp()).sum();
+        let leaves = leaves
+            .into_iter()
+            .zip(log_weights)
+            .map(|(leave, w)| (leave, (w - max).exp() / total))
+            .collect();
+        Ok(OpponentModel { leaves, unseen })
+    }
+
+    /// Return the possible leaves with their probability
+    pub fn leaves(&self) -> &[(Letters, f64)] {
+        &self.leaves
+    }
+
+    /// Draw `n` opponent racks of `rack_size` letters: a leave drawn by probability,
+    /// refilled with random unseen tiles.
+    /// ## Errors
+    /// If an unseen tile is not a valid letter code.
+    pub fn sample<R: Rng>(
+        &self,
+        n: usize,
+        rack_size: usize,
+        rng: &mut R,
+    ) -> Result<Vec<Letters>, Error> {
+        let index = WeightedIndex::new(self.leaves.iter().map(|&(_, p)| p)).ok();
+        (0..n)
+            .map(|_| {
+                let leave = match &index {
+                    Some(index) => self.leaves[index.sample(rng)].0,
+                    None => Letters::new(),
+                };
+                let mut pool = self.unseen.clone();
+                for letter in leave {
+                    if let Some(i) = pool.iter().position(|&code| code == letter.code()) {
+                        pool.swap_remove(i);
+                    }
+                }
+                let nrefill = rack_size.saturating_sub(leave.len()).min(pool.len());
+                let (drawn, _) = pool.partial_shuffle(rng, nrefill);
+                let mut rack = leave;
+                for &code in drawn.iter() {
+                    rack.push(Letter::try_from(code)?);
+                }
+                Ok(rack)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Language;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    type Result<T> = std::result::Result<T, Error>;
+
+    #[test]
+    fn test_infer() -> Result<()> {
+        let board = Board::new(Language::EN).with_wordlist_from_words(&["ab", "za"])?;
+        let rack: Letters = board.encode("eeeeeee")?;
+        // the opponent played the best "ab", so they did not have a "z"
+        let mut words = board.calc_all_word_scores("ab")?;
+        words.sort_by_key(|s| std::cmp::Reverse(s.score));
+        let s = words[0];
+        let last_move = Move::Tiles(board.word_placement(s.word, s.x, s.y, s.horizontal));
+        let mut rng = StdRng::seed_from_u64(5);
+        let model = OpponentModel::infer(&board, &last_move, rack, 200, &mut rng)?;
+        assert_eq!(model.leaves().len(), 200);
+        let total: f64 = model.leaves().iter().map(|&(_, p)| p).sum();
+        assert!((total - 1.0).abs() < 1e-9);
+
+        let z = board.encode("z")?;
+        let has_z = |leave: &Letters| leave.remove_letters(z).is_ok();
+        let (with_z, without_z): (Vec<_>, Vec<_>) =
+            model.leaves().iter().partition(|(leave, _)| has_z(leave));
+        assert!(!with_z.is_empty());
+        let max_with_z = with_z.iter().map(|&&(_, p)| p).fold(0.0, f64::max);
+        assert!(without_z.iter().all(|&&(_, p)| p > max_with_z));
+
+        let racks = model.sample(100, RACK_SIZE, &mut rng)?;
+        assert!(racks.iter().all(|rack| rack.len() == RACK_SIZE));
+        let nz = |racks: &[Letters]| racks.iter().filter(|rack| has_z(rack)).count();
+        assert!(nz(&racks) < 10);
+        Ok(())
+    }
+
+    #[test]
+    fn test_infer_swap() -> Result<()> {
+        let board = Board::new(Language::EN).with_wordlist_from_words(&["ab"])?;
+        let rack: Letters = board.encode("eeeeeee")?;
+        let swapped: Letters = board.encode("abcd")?;
+        let mut rng = StdRng::seed_from_u64(5);
+        let model = OpponentModel::infer(&board, &Move::Swap(swapped), rack, 50, &mut rng)?;
+        assert!(model.leaves().iter().all(|(leave, _)| leave.len() == 3));
+        // the best leave is more likely than the worst leave
+        let table = LeaveTable::new(Language::EN);
+        let best = model
+            .leaves()
+            .iter()
+            .max_by(|a, b| table.evaluate(a.0).total_cmp(&table.evaluate(b.0)))
+            .unwrap();
+        let worst = model
+            .leaves()
+            .iter()
+            .min_by(|a, b| table.evaluate(a.0).total_cmp(&table.evaluate(b.0)))
+            .unwrap();
+        assert!(best.1 > worst.1);
+        Ok(())
+    }
+}
@@ -0,0 +1,352 @@
+use crate::{Error, Game, Language, Letter, Letters, Move, TileSet};
+use std::fmt;
+use std::fs::read_to_string;
+
+/// Number of letter codes (labels 1..31, 0 is unused)
+const NLETTERS: usize = 32;
+
+/// Number of features: one per letter code, plus blank, duplicate, balance and q without u
+const NFEATURES: usize = NLETTERS + 4;
+
+/// Step size and number of steps of the gradient descent in [`LeaveTable::fit`]
+const LEARNING_RATE: f32 = 0.01;
+const FIT_STEPS: usize = 500;
+
+/// Values used to evaluate the letters that are left on the rack after a move (the "leave").
+///
+/// The value of a leave is the sum of:
+/// - the value of each letter,
+/// - `blank` for each blank,
+/// - `duplicate` for each letter that is already in the leave,
+/// - `balance` times the difference between the number of vowels and consonants,
+/// - `q_without_u` if the leave has a "q" but no "u" or blank.
+///
+/// A table can be created with default values for a [`Language`], loaded from a file
+/// with [`from_file`](LeaveTable::from_file), and fitted to recorded games with [`fit`](LeaveTable::fit).
+#[derive(Debug, Clone)]
+pub struct LeaveTable {
+    language: Language,
+    labels: Vec<&'static str>,
+    vowels: [bool; NLETTERS],
+    q_u: Option<(usize, usize)>,
+    /// value of each letter, indexed by letter code
+    pub letters: [f32; NLETTERS],
+    /// value of a blank
+    pub blank: f32,
+    /// value for each duplicate letter
+    pub duplicate: f32,
+    /// value for each vowel or consonant more than the other
+    pub balance: f32,
+    /// value of a "q" without a "u"
+    pub q_without_u: f32,
+}
+
+impl LeaveTable {
+    /// Return a table with the default values for `language`.
+    /// The value of a letter decreases with its points: letters with high points are harder to play.
+    pub fn new(language: Language) -> LeaveTable {
+        let tileset = TileSet::new(language.clone());
+        let vowel_labels: &[&str] = match language {
+            Language::EN | Language::NL => &["a", "e", "i", "o", "u"],
+            Language::SE => &["a", "e", "i", "o", "u", "y", "å", "ä", "ö"],
+        };
+        let mut labels = vec![""; NLETTERS];
+        let mut vowels = [false; NLETTERS];
+        let mut letters = [0.0; NLETTERS];
+        for (code, &(label, count, points)) in tileset.tiles.iter().enumerate().skip(1) {
+            if code < NLETTERS && count > 0 {
+                labels[code] = label;
+                vowels[code] = vowel_labels.contains(&label);
+                letters[code] = 1.5 - 0.5 * points as f32;
+            }
+        }
+        let find = |label: &str| labels.iter().position(|&l| l == label);
+        let q_u = find("q").zip(find("u"));
+        LeaveTable {
+            language,
+            labels,
+            vowels,
+            q_u,
+            letters,
+            blank: 10.0,
+            duplicate: -2.0,
+            balance: -1.0,
+            q_without_u: -7.0,
+        }
+    }
+
+    /// Read the table for `language` from `path`.
+    ///
+    /// Each line contains a name and a value, separated by whitespace.
+    /// The name is a letter, or one of `blank`, `duplicate`, `balance` and `q_without_u`.
+    /// Empty lines and lines starting with `#` are ignored.
+    /// Values that are not in the file keep their default value.
+    /// A table in this format is written by the [`Display`](fmt::Display) implementation.
+    /// ## Errors
+    /// If the file can not be read, or has a line with an unknown name or invalid value.
+    pub fn from_file(path: &str, language: Language) -> Result<LeaveTable, Error> {
+        let mut table = LeaveTable::new(language);
+        let text = read_to_string(path).map_err(|source| Error::ReadError {
+            path: String::from(path),
+            source,
+        })?;
+        for line in text.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let mut fields = line.split_whitespace();
+            let parse_error = || Error::LeaveTableParseError(String::from(line));
+            let name = fields.next().ok_or_else(parse_error)?;
+            let value: f32 = fields
+                .next()
+                .and_then(|value| value.parse().ok())
+                .ok_or_else(parse_error)?;
+            if fields.next().is_some() {
+                return Err(parse_error());
+            }
+            match name {
+                "blank" => table.blank = value,
+                "duplicate" => table.duplicate = value,
+                "balance" => table.balance = value,
+                "q_without_u" => table.q_without_u = value,
+                _ => {
+                    let code = table
+                        .labels
+                        .iter()
+                        .position(|&label| !label.is_empty() && label == name)
+                        .ok_or_else(parse_error)?;
+                    table.letters[code] = value;
+                }
+            }
+        }
+        Ok(table)
+    }
+
+    /// Return the language of the table
+    pub fn language(&self) -> &Language {
+        &self.language
+    }
+
+    /// Return the value of `leave`.
+    pub fn evaluate(&self, leave: Letters) -> f32 {
+        self.features(leave)
+            .iter()
+            .zip(self.weights().iter())
+            .map(|(f, w)| f * w)
+            .sum()
+    }
+
+    /// Fit the values to `samples` of leaves with an observed value, such as
+    /// the leave samples of recorded games from [`leave_samples`].
+    /// The values are fitted with gradient descent on the mean squared error,
+    /// starting from the current values.
+    pub fn fit(&mut self, samples: &[(Letters, f32)]) {
+        if samples.is_empty() {
+            return;
+        }
+        let features: Vec<[f32; NFEATURES]> = samples
+            .iter()
+            .map(|&(leave, _)| self.features(leave))
+            .collect();
+        let mut weights = self.weights();
+        let n = samples.len() as f32;
+        for _ in 0..FIT_STEPS {
+            let mut gradient = [0.0; NFEATURES];
+            for (f, &(_, target)) in features.iter().zip(samples) {
+                let predicted: f32 = f.iter().zip(weights.iter()).map(|(f, w)| f * w).sum();
+                let error = predicted - target;
+                for (g, f) in gradient.iter_mut().zip(f.iter()) {
+                    *g += error * f / n;
+                }
+            }
+            for (w, g) in weights.iter_mut().zip(gradient.iter()) {
+                *w -= LEARNING_RATE * g;
+            }
+        }
+        self.set_weights(&weights);
+    }
+
+    fn features(&self, leave: Letters) -> [f32; NFEATURES] {
+        let mut features = [0.0; NFEATURES];
+        let mut counts = [0; NLETTERS];
+        let mut blanks = 0;
+        for letter in leave {
+            if letter.is_blank() {
+                blanks += 1;
+            } else {
+                counts[letter.label() as usize] += 1;
+            }
+        }
+        let (mut vowels, mut consonants) = (0_i32, 0_i32);
+        for (code, &count) in counts.iter().enumerate() {
+            features[code] = count as f32;
+            if self.vowels[code] {
+                vowels += count;
+            } else {
+                consonants += count;
+            }
+        }
+        features[NLETTERS] = blanks as f32;
+        features[NLETTERS + 1] = counts.iter().map(|&c| (c - 1).max(0)).sum::<i32>() as f32;
+        features[NLETTERS + 2] = (vowels - consonants).abs() as f32;
+        if let Some((q, u)) = self.q_u {
+            if counts[q] > 0 && counts[u] == 0 && blanks == 0 {
+                features[NLETTERS + 3] = 1.0;
+            }
+        }
+        features
+    }
+
+    fn weights(&self) -> [f32; NFEATURES] {
+        let mut weights = [0.0; NFEATURES];
+        weights[..NLETTERS].copy_from_slice(&self.letters);
+        weights[NLETTERS] = self.blank;
+        weights[NLETTERS + 1] = self.duplicate;
+        weights[NLETTERS + 2] = self.balance;
+        weights[NLETTERS + 3] = self.q_without_u;
+        weights
+    }
+
+    fn set_weights(&mut self, weights: &[f32; NFEATURES]) {
+        self.letters.copy_from_slice(&weights[..NLETTERS]);
+        self.blank = weights[NLETTERS];
+        self.duplicate = weights[NLETTERS + 1];
+        self.balance = weights[NLETTERS + 2];
+        self.q_without_u = weights[NLETTERS + 3];
+    }
+}
+
+/// Write the table in the format read by [`LeaveTable::from_file`].
+impl fmt::Display for LeaveTable {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "# leave table {:?}", self.language)?;
+        for (label, value) in self.labels.iter().zip(self.letters.iter()) {
+            if !label.is_empty() {
+                writeln!(f, "{} {}", label, value)?;
+            }
+        }
+        writeln!(f, "blank {}", self.blank)?;
+        writeln!(f, "duplicate {}", self.duplicate)?;
+        writeln!(f, "balance {}", self.balance)?;
+        writeln!(f, "q_without_u {}", self.q_without_u)
+    }
+}
+
+/// Return the leaves of the moves in a recorded `game`, with their observed value:
+/// the score of the next move of the player, relative to the mean score per move in the game.
+/// A leave that is followed by a good move has a positive value.
+pub fn leave_samples(game: &Game) -> Vec<(Letters, f32)> {
+    let turns = game.turns();
+    if turns.is_empty() {
+        return Vec::new();
+    }
+    let mean_score = turns.iter().map(|turn| turn.score as f32).sum::<f32>() / turns.len() as f32;
+    let mut samples = Vec::new();
+    for (i, turn) in turns.iter().enumerate() {
+        let used: Letters = match &turn.action {
+            Move::Tiles(placement) => placement
+                .iter()
+                .map(|&(_, _, tile)| Letter::from_tile(tile))
+                .collect(),
+            Move::Swap(letters) => *letters,
+            Move::Pass => continue,
+        };
+        let leave = match turn.rack.remove_letters(used) {
+            Ok(leave) => leave,
+            Err(_) => continue,
+        };
+        let next = turns[i + 1..]
+            .iter()
+            .find(|next| next.player == turn.player);
+        if let Some(next) = next {
+            samples.push((leave, next.score as f32 - mean_score));
+        }
+    }
+    samples
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Board, List};
+    use std::io::Write;
+
+    type Result<T> = std::result::Result<T, Error>;
+
+    #[test]
+    fn test_evaluate() -> Result<()> {
+        let board = Board::new(Language::EN);
+        let table = LeaveTable::new(Language::EN);
+        let value = |letters: &str| -> Result<f32> { Ok(table.evaluate(board.encode(letters)?)) };
+        assert_eq!(value("")?, 0.0);
+        // a blank is better than a regular letter
+        assert!(value("*")? > value("e")?);
+        // a duplicate letter is worse than two different letters
+        assert!(value("ee")? < value("er")?);
+        // balanced letters are better than only consonants
+        assert!(value("ert")? > value("rst")?);
+        // a q is better with a u
+        assert!(value("qu")? > value("qi")?);
+        assert!(value("q*")? > value("q")?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_file() -> Result<()> {
+        let mut table = LeaveTable::new(Language::SE);
+        table.blank = 25.0;
+        let code = TileSet::new(Language::SE).codec().encode("å")?[0];
+        table.letters[usize::from(code)] = 3.0;
+        let path =
+            std::env::temp_dir().join(format!("wordfeud_leave_se_{}.txt", std::process::id()));
+        let mut file = std::fs::File::create(&path).unwrap();
+        write!(file, "{}", table).unwrap();
+        let path = path.to_str().unwrap();
+        let loaded = LeaveTable::from_file(path, Language::SE)?;
+        assert_eq!(loaded.letters, table.letters);
+        assert_eq!(loaded.blank, 25.0);
+
+        std::fs::write(path, "blank 1.0\nhello 2.0\n").unwrap();
+        assert!(matches!(
+            LeaveTable::from_file(path, Language::SE),
+            Err(Error::LeaveTableParseError(line)) if line == "hello 2.0"
+        ));
+        std::fs::remove_file(path).unwrap();
+        Ok(())
+    }
+
+    #[test]
+    fn test_fit() -> Result<()> {
+        let board = Board::new(Language::EN);
+        let mut table = LeaveTable::new(Language::EN);
+        // leaves with an "s" score 10 points more
+        let samples: Vec<(Letters, f32)> = ["s", "es", "rs", "e", "r", "er"]
+            .iter()
+            .map(|&letters| {
+                let leave: Letters = board.encode(letters).unwrap();
+                let value = if letters.contains('s') { 10.0 } else { 0.0 };
+                (leave, value)
+            })
+            .collect();
+        let before = table.evaluate(board.encode("s")?) - table.evaluate(board.encode("r")?);
+        table.fit(&samples);
+        let after = table.evaluate(board.encode("s")?) - table.evaluate(board.encode("r")?);
+        assert!(after > before + 5.0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_leave_samples() -> Result<()> {
+        let board = Board::new(Language::NL).with_wordlist_from_words(&["af", "al", "bel"])?;
+        let mut game = Game::new(board, 2, 3)?;
+        let swapped: Letters = game.rack().iter().take(3).cloned().collect();
+        let rack = game.rack();
+        game.play(Move::Swap(swapped))?;
+        game.play(Move::Pass)?;
+        game.play(Move::Pass)?;
+        let samples = leave_samples(&game);
+        assert_eq!(samples.len(), 1);
+        assert_eq!(samples[0].0, rack.remove_letters(swapped).unwrap());
+        Ok(())
+    }
+}
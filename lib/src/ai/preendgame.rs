@@ -0,0 +1,157 @@
+use crate::game::RACK_SIZE;
+use crate::{Code, Error, Letter, Letters, List};
+use std::convert::TryFrom;
+
+/// A possible division of the unseen tiles between the opponent rack and the bag.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct RackSplit {
+    /// tiles on the opponent rack
+    pub rack: Letters,
+    /// tiles in the bag
+    pub bag: Letters,
+    /// probability of this split
+    pub probability: f64,
+}
+
+/// Return every possible split of the `unseen` tiles between the opponent rack and the bag.
+///
+/// The opponent rack holds 7 tiles (or all unseen tiles if there are less), the bag the rest.
+/// Splits with the same letters are returned once, with the sum of their probabilities.
+///
+/// Every split is evaluated for each candidate move, and with 14 unseen tiles there can be
+/// up to 3432 splits. If `max_splits` is set and there are more splits, only the `max_splits`
+/// most probable splits are returned, with their probabilities scaled to sum to 1.
+/// ## Errors
+/// If an unseen tile is not a valid letter code.
+pub(crate) fn rack_splits(
+    unseen: &[Code],
+    max_splits: Option<usize>,
+) -> Result<Vec<RackSplit>, Error> {
+    let mut groups: Vec<(Code, usize)> = Vec::new();
+    let mut codes = unseen.to_vec();
+    codes.sort_unstable();
+    for code in codes {
+        match groups.last_mut() {
+            Some((last, count)) if *last == code => *count += 1,
+            _ => groups.push((code, 1)),
+        }
+    }
+    let nrack = unseen.len().min(RACK_SIZE);
+    let total = binomial(unseen.len(), nrack);
+    let mut splits = Vec::new();
+    let mut counts = vec![0; groups.len()];
+    split_groups(&groups, &mut counts, 0, nrack, 1.0, &mut |counts, ways| {
+        let mut rack = Letters::new();
+        let mut bag = Letters::new();
+        for (&(code, count), &n) in groups.iter().zip(counts) {
+            let letter = Letter::try_from(code)?;
+            for i in 0..count {
+                if i < n {
+                    rack.push(letter);
+                } else {
+                    bag.push(letter);
+                }
+            }
+        }
+        splits.push(RackSplit {
+            rack,
+            bag,
+            probability: ways / total,
+        });
+        Ok(())
+    })?;
+    if let Some(max_splits) = max_splits.filter(|&max_splits| splits.len() > max_splits) {
+        splits.sort_by(|a, b| b.probability.total_cmp(&a.probability));
+        splits.truncate(max_splits);
+        let kept: f64 = splits.iter().map(|split| split.probability).sum();
+        for split in &mut splits {
+            split.probability /= kept;
+        }
+    }
+    Ok(splits)
+}
+
+/// Choose `counts[i]` tiles of each group `i` >= `start`, so that `needed` tiles are chosen,
+/// and call `f` with the counts and the number of ways to choose them.
+fn split_groups<F>(
+    groups: &[(Code, usize)],
+    counts: &mut Vec<usize>,
+    start: usize,
+    needed: usize,
+    ways: f64,
+    f: &mut F,
+) -> Result<(), Error>
+where
+    F: FnMut(&[usize], f64) -> Result<(), Error>,
+{
+    if start == groups.len() {
+        return if needed == 0 { f(counts, ways) } else { Ok(()) };
+    }
+    let count = groups[start].1;
+    for n in 0..=count.min(needed) {
+        counts[start] = n;
+        split_groups(
+            groups,
+            counts,
+            start + 1,
+            needed - n,
+            ways * binomial(count, n),
+            f,
+        )?;
+    }
+    counts[start] = 0;
+    Ok(())
+}
+
+fn binomial(n: usize, k: usize) -> f64 {
+    (0..k).fold(1.0, |acc, i| acc * (n - i) as f64 / (i + 1) as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Board, Item, Language};
+
+    #[test]
+    fn test_rack_splits() -> Result<(), Error> {
+        let board = Board::new(Language::EN);
+        let unseen: Letters = board.encode("aaabcdefgh")?;
+        let codes: Vec<Code> = unseen
+            .into_iter()
+            .map(|letter: Letter| letter.code())
+            .collect();
+        let splits = rack_splits(&codes, None)?;
+        let total: f64 = splits.iter().map(|split| split.probability).sum();
+        assert!((total - 1.0).abs() < 1e-9);
+        for split in &splits {
+            assert_eq!(split.rack.len(), 7);
+            assert_eq!(split.bag.len(), 3);
+        }
+        // the bag holds "aaa" in 1 of the 120 ways to choose 3 of 10 tiles
+        let aaa: Letters = board.encode("aaa")?;
+        let split = splits.iter().find(|split| split.bag == aaa).unwrap();
+        assert!((split.probability - 1.0 / 120.0).abs() < 1e-9);
+
+        // only the most probable splits are kept
+        let most_probable = rack_splits(&codes, Some(10))?;
+        assert_eq!(most_probable.len(), 10);
+        let total: f64 = most_probable.iter().map(|split| split.probability).sum();
+        assert!((total - 1.0).abs() < 1e-9);
+        let kept = |split: &&RackSplit| most_probable.iter().any(|kept| kept.rack == split.rack);
+        let min_kept = splits
+            .iter()
+            .filter(kept)
+            .map(|split| split.probability)
+            .fold(1.0, f64::min);
+        assert!(splits
+            .iter()
+            .filter(|split| !kept(split))
+            .all(|split| split.probability <= min_kept));
+
+        // with 7 or less unseen tiles they are all on the opponent rack
+        let splits = rack_splits(&codes[..5], None)?;
+        assert_eq!(splits.len(), 1);
+        assert!(splits[0].bag.is_empty());
+        Ok(())
+    }
+}
@@ -0,0 +1,523 @@
+use super::{
+    evaluate_leaves, mean, remaining_tiles, std_deviation, swap_candidates, with_threads, ExitFlag,
+    LeaveTable, MoveKind, Score, SearchConfig, MAX_SWAPS, Z_95,
+};
+use crate::game::RACK_SIZE;
+use crate::placement::Placement;
+use crate::tilebag::TileBag;
+use crate::{Board, Code, Error, Item, Letter, Letters, List};
+use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+use std::convert::TryFrom;
+use std::time::Instant;
+
+/// A move with the results of its rollouts in a [`Simulation`].
+#[derive(Debug, Clone)]
+pub struct SimScore {
+    /// the move. `opp_score` and `opp_std` are the mean and standard deviation of the opponent replies,
+    /// and `adj_score` is the rounded `value`
+    pub score: Score,
+    /// mean value of the rollouts: our score, minus the opponent reply, plus our next reply
+    pub value: f32,
+    /// mean score of our next reply
+    pub next_score: f32,
+    /// standard deviation of our next reply
+    pub next_std: f32,
+    /// number of rollouts
+    pub rollouts: usize,
+    /// 95% confidence interval of `value`
+    pub interval: (f32, f32),
+    /// false if the move was eliminated from the simulation
+    pub alive: bool,
+}
+
+struct Candidate {
+    score: Score,
+    placement: Option<Placement>,
+    used: Letters,
+    opp_scores: Vec<i32>,
+    next_scores: Vec<i32>,
+    alive: bool,
+}
+
+impl Candidate {
+    fn new(
+        board: &Board,
+        mut score: Score,
+        placement: Option<Placement>,
+        used: Letters,
+        kind: MoveKind,
+    ) -> Candidate {
+        score.played = board.decode(used);
+        score.kind = kind;
+        Candidate {
+            score,
+            placement,
+            used,
+            opp_scores: Vec::new(),
+            next_scores: Vec::new(),
+            alive: true,
+        }
+    }
+
+    fn value(&self) -> f32 {
+        self.score.score as f32 - mean(&self.opp_scores).unwrap_or(0.0)
+            + mean(&self.next_scores).unwrap_or(0.0)
+    }
+}
+
+/// Anytime Monte Carlo simulation to select a move.
+///
+/// Each candidate move is evaluated with rollouts of three plies:
+/// - we play the move,
+/// - the opponent replies with the best move for a random rack. After a swap,
+///   the rack is drawn from the unseen tiles and the letters we swapped,
+/// - we reply with the best move for our leave, refilled with random tiles.
+///
+/// The opponent reply is played on the board before our next reply is calculated, and then undone.
+/// To play it, the best opponent move is taken from [`calc_all_word_scores`](Board::calc_all_word_scores);
+/// its score is the best score that [`sample_scores`](Board::sample_scores) returns for the rack,
+/// so outside the endgame the opponent is scored like in [`find_best_scores`](crate::find_best_scores).
+/// Our next reply is scored with [`sample_scores`](Board::sample_scores).
+/// The rollouts of a candidate run in parallel with the `rayon` feature.
+///
+/// The simulation runs in rounds. In each round every remaining candidate gets `samples` rollouts,
+/// and then the worst half of the candidates is eliminated (successive halving),
+/// so the most promising moves get the most rollouts.
+/// The simulation can be stopped after any round, and [`ranking`](Simulation::ranking)
+/// returns the current ranking with confidence intervals.
+///
+/// When the `time_budget` of the [`SearchConfig`] is used, counted from the creation of the simulation,
+/// no more rollouts are started, except the first rollout of a candidate.
+///
+/// The candidates are the `candidates` best words of the [`SearchConfig`],
+/// and if `swaps` is true, the [`MAX_SWAPS`] swaps with the best leave.
+/// With an empty bag, use [`solve_endgame`](crate::solve_endgame) instead.
+pub struct Simulation<'a> {
+    board: Board<'a>,
+    rack: Letters,
+    tiles: Vec<Code>,
+    rack_size: usize,
+    samples: usize,
+    rng: StdRng,
+    candidates: Vec<Candidate>,
+    rounds: usize,
+    deadline: Option<Instant>,
+}
+
+impl<'a> Simulation<'a> {
+    /// Prepare a simulation of the moves with our letters in `rack` on `board`, with the parameters in `config`.
+    /// `threads` of `config` is used by [`simulate`].
+    /// ## Errors
+    /// If the word scores can not be calculated, see [calc_all_word_scores](crate::Board::calc_all_word_scores).
+    pub fn new(
+        board: &Board<'a>,
+        rack: Letters,
+        config: &SearchConfig,
+    ) -> Result<Simulation<'a>, Error> {
+        let full_bag = TileBag::from(board.tileset());
+        let tiles = remaining_tiles(&full_bag, board, rack).sorted();
+
+        let mut words = board.calc_all_word_scores(rack)?;
+        words.sort_by(|a, b| b.cmp(a));
+        let mut candidates = Vec::new();
+        for s in words.into_iter().take(config.candidates) {
+            let placement = board.word_placement(s.word, s.x, s.y, s.horizontal);
+            let used: Letters = placement
+                .iter()
+                .map(|&(_, _, tile)| Letter::from_tile(tile))
+                .collect();
+            let score = move_score(s.x, s.y, s.horizontal, board.decode(s.word), s.score as i32);
+            candidates.push(Candidate::new(
+                board,
+                score,
+                Some(placement),
+                used,
+                MoveKind::Word,
+            ));
+        }
+
+        let table = match &config.leave {
+            Some(table) => table.clone(),
+            None => LeaveTable::new(board.tileset().language.clone()),
+        };
+        // we can swap if there are 7 tiles in the bag, besides the 7 tiles of the opponent
+        if config.swaps && tiles.len() >= 2 * RACK_SIZE {
+            let mut swaps = swap_candidates(rack);
+            swaps.sort_by(|&a, &b| {
+                let leave = |swapped| rack.remove_letters(swapped).unwrap_or_default();
+                table
+                    .evaluate(leave(b))
+                    .total_cmp(&table.evaluate(leave(a)))
+            });
+            for swapped in swaps.into_iter().take(MAX_SWAPS) {
+                let score = move_score(0, 0, true, String::new(), 0);
+                candidates.push(Candidate::new(board, score, None, swapped, MoveKind::Swap));
+            }
+        }
+        let mut scores: Vec<Score> = candidates.iter().map(|c| c.score.clone()).collect();
+        evaluate_leaves(board, rack, &mut scores, &table)?;
+        for (c, s) in candidates.iter_mut().zip(scores) {
+            c.score = s;
+        }
+
+        let rng = match config.seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_entropy(),
+        };
+        Ok(Simulation {
+            board: board.clone(),
+            rack,
+            tiles,
+            rack_size: config.rack_size,
+            samples: config.samples.max(1),
+            rng,
+            candidates,
+            rounds: 0,
+            deadline: config.time_budget.map(|budget| Instant::now() + budget),
+        })
+    }
+
+    /// Check if the simulation is finished: one candidate is left, or the time budget is used.
+    pub fn is_finished(&self) -> bool {
+        self.candidates.iter().filter(|c| c.alive).count() <= 1 || self.out_of_time()
+    }
+
+    fn out_of_time(&self) -> bool {
+        self.deadline
+            .is_some_and(|deadline| Instant::now() >= deadline)
+    }
+
+    /// Return the number of rounds that were run
+    pub fn rounds(&self) -> usize {
+        self.rounds
+    }
+
+    /// Run one round: `samples` rollouts for each remaining candidate,
+    /// then eliminate the worst half of the candidates.
+    /// If the time budget is used during the round, the remaining rollouts are skipped
+    /// and no candidates are eliminated.
+    /// Returns false if the simulation was already finished.
+    /// ## Errors
+    /// If the opponent scores can not be calculated, see [`Board::sample_scores`].
+    pub fn step(&mut self) -> Result<bool, Error> {
+        if self.is_finished() && self.rounds > 0 {
+            return Ok(false);
+        }
+        for i in 0..self.candidates.len() {
+            if self.candidates[i].alive {
+                self.rollouts(i)?;
+            }
+        }
+        self.rounds += 1;
+        if self.out_of_time() {
+            return Ok(true);
+        }
+        let mut alive: Vec<usize> = (0..self.candidates.len())
+            .filter(|&i| self.candidates[i].alive)
+            .collect();
+        alive.sort_by(|&a, &b| {
+            self.candidates[b]
+                .value()
+                .total_cmp(&self.candidates[a].value())
+        });
+        let keep = alive.len().div_ceil(2);
+        for &i in &alive[keep..] {
+            self.candidates[i].alive = false;
+        }
+        Ok(true)
+    }
+
+    /// Return the current ranking of the candidates, best first.
+    /// The remaining candidates are ranked before the eliminated candidates.
+    pub fn ranking(&self) -> Vec<SimScore> {
+        let mut ranking: Vec<SimScore> = self
+            .candidates
+            .iter()
+            .map(|c| {
+                let opp_score = mean(&c.opp_scores).unwrap_or(0.0);
+                let opp_std = std_deviation(&c.opp_scores).unwrap_or(0.0);
+                let next_score = mean(&c.next_scores).unwrap_or(0.0);
+                let next_std = std_deviation(&c.next_scores).unwrap_or(0.0);
+                let value = c.value();
+                let rollouts = c.opp_scores.len();
+                // the replies are assumed to be independent
+                let half_width = if rollouts > 0 {
+                    Z_95 * (opp_std.powi(2) + next_std.powi(2)).sqrt() / (rollouts as f32).sqrt()
+                } else {
+                    f32::INFINITY
+                };
+                let mut score = c.score.clone();
+                score.adj_score = value.round() as i32;
+                score.opp_score = opp_score.round() as i32;
+                score.opp_std = opp_std;
+                SimScore {
+                    score,
+                    value,
+                    next_score,
+                    next_std,
+                    rollouts,
+                    interval: (value - half_width, value + half_width),
+                    alive: c.alive,
+                }
+            })
+            .collect();
+        ranking.sort_by(|a, b| {
+            b.alive
+                .cmp(&a.alive)
+                .then_with(|| b.value.total_cmp(&a.value))
+        });
+        ranking
+    }
+
+    /// Run `samples` rollouts for candidate `i`, or less if the time budget is used.
+    fn rollouts(&mut self, i: usize) -> Result<(), Error> {
+        let c = &self.candidates[i];
+        let leave = self.rack.remove_letters(c.used).unwrap_or_default();
+        let mut racks = Vec::with_capacity(self.samples);
+        let mut pool = self.tiles.clone();
+        for _ in 0..self.samples {
+            if c.score.kind == MoveKind::Swap {
+                // we draw new letters before the swapped letters go back to the bag,
+                // and then the opponent rack is drawn from the unseen tiles with the swapped letters
+                let nrefill = c.used.len().min(pool.len());
+                let (drawn, rest) = pool.partial_shuffle(&mut self.rng, nrefill);
+                let mut next = leave;
+                for &code in drawn.iter() {
+                    next.push(Letter::try_from(code)?);
+                }
+                let mut unseen = rest.to_vec();
+                unseen.extend(c.used.into_iter().map(|letter: Letter| letter.code()));
+                let nopp = self.rack_size.min(unseen.len());
+                let (drawn, _) = unseen.partial_shuffle(&mut self.rng, nopp);
+                racks.push((Letters::try_from(drawn.to_vec())?, next));
+                continue;
+            }
+            let nopp = self.rack_size.min(pool.len());
+            let nrefill = c.used.len().min(pool.len() - nopp);
+            let (drawn, _) = pool.partial_shuffle(&mut self.rng, nopp + nrefill);
+            let opp = Letters::try_from(drawn[..nopp].to_vec())?;
+            let mut next = leave;
+            for &code in &drawn[nopp..] {
+                next.push(Letter::try_from(code)?);
+            }
+            racks.push((opp, next));
+        }
+
+        // the first rollout of a candidate is always run, so every candidate can be ranked
+        let first = c.opp_scores.is_empty();
+        let deadline = self.deadline;
+        let run = |board: &mut Board, k: usize, &(opp, next): &(Letters, Letters)| {
+            if (k > 0 || !first) && deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+                None
+            } else {
+                Some(rollout(board, opp, next))
+            }
+        };
+        let record = match &c.placement {
+            Some(placement) => Some(self.board.place(placement)?.1),
+            None => None,
+        };
+        let results: Vec<_>;
+        #[cfg(not(feature = "rayon"))]
+        {
+            let board = &mut self.board;
+            results = racks
+                .iter()
+                .enumerate()
+                .map(|(k, racks)| run(board, k, racks))
+                .collect();
+        }
+        #[cfg(feature = "rayon")]
+        {
+            let board = &self.board;
+            results = racks
+                .par_iter()
+                .enumerate()
+                .map_init(|| board.clone(), |board, (k, racks)| run(board, k, racks))
+                .collect();
+        }
+        if let Some(record) = &record {
+            self.board.unplace(record);
+        }
+        let replies = results
+            .into_iter()
+            .flatten()
+            .collect::<Result<Vec<_>, _>>()?;
+        let c = &mut self.candidates[i];
+        c.opp_scores.extend(replies.iter().map(|&(opp, _)| opp));
+        c.next_scores.extend(replies.iter().map(|&(_, next)| next));
+        Ok(())
+    }
+}
+
+/// The best score with `letters` on `board`, or 0 if no word can be played,
+/// calculated with [`Board::sample_scores`] like the opponent scores of `find_best_scores`.
+fn best_score(board: &Board, letters: Letters) -> Result<i32, Error> {
+    Ok(board.sample_scores(&[letters], 0, false)?[0].0 as i32)
+}
+
+/// Play the best move of the opponent with `opp_rack` on `board`, and calculate our best reply with `next_rack`.
+/// Returns both scores, and leaves the board as it was.
+///
+/// The opponent move is found with [`Board::calc_all_word_scores`], because it is played on the board,
+/// and [`Board::sample_scores`] only returns the score.
+fn rollout(board: &mut Board, opp_rack: Letters, next_rack: Letters) -> Result<(i32, i32), Error> {
+    let opp = match board.calc_all_word_scores(opp_rack)?.into_iter().max() {
+        Some(opp) => opp,
+        // the opponent passes
+        None => return Ok((0, best_score(board, next_rack)?)),
+    };
+    let placement = board.word_placement(opp.word, opp.x, opp.y, opp.horizontal);
+    let (_, record) = board.place(&placement)?;
+    let next = best_score(board, next_rack);
+    board.unplace(&record);
+    Ok((opp.score as i32, next?))
+}
+
+fn move_score(x: usize, y: usize, horizontal: bool, word: String, score: i32) -> Score {
+    Score {
+        x,
+        y,
+        horizontal,
+        word,
+        score,
+        adj_score: score,
+        opp_score: 0,
+        opp_std: 0.0,
+        played: String::new(),
+        exit_flag: ExitFlag::None,
+        opp_score_diff: 0,
+        kind: MoveKind::Word,
+        leave_score: 0.0,
+        risk: 0.0,
+        bingo_bonus: 0.0,
+        swap_gain: 0.0,
+    }
+}
+
+/// Run a [`Simulation`] of the moves with our letters in `rack` on `board`, with the parameters in `config`.
+///
+/// Rounds are run until one candidate is left, or until the `time_budget` is used.
+/// At least one round is run, with at least one rollout for each candidate.
+/// Returns the ranking of the candidates, best first.
+/// ## Errors
+/// If the scores can not be calculated, or if the thread pool with `threads` threads can not be created.
+pub fn simulate(
+    board: &Board,
+    rack: Letters,
+    config: &SearchConfig,
+) -> Result<Vec<SimScore>, Error> {
+    with_threads(config.threads, || {
+        let mut simulation = Simulation::new(board, rack, config)?;
+        while simulation.step()? {}
+        Ok(simulation.ranking())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures;
+    use std::time::Duration;
+
+    type Result<T> = std::result::Result<T, Error>;
+
+    fn board<'a>() -> Result<Board<'a>> {
+        fixtures::board()?.with_state_from_strings(&[
+            "...............",
+            "...............",
+            "...............",
+            "...............",
+            "...............",
+            "...............",
+            "...............",
+            ".....rust......",
+            "...............",
+            "...............",
+            "...............",
+            "...............",
+            "...............",
+            "...............",
+            "...............",
+        ])
+    }
+
+    #[test]
+    fn test_simulate() -> Result<()> {
+        let board = board()?;
+        let rack: Letters = board.encode("tesnore")?;
+        let config = SearchConfig {
+            candidates: 8,
+            samples: 4,
+            ..SearchConfig::default()
+        };
+        let ranking = simulate(&board, rack, &config)?;
+        assert_eq!(ranking.len(), 8 + MAX_SWAPS);
+        // one candidate is left, and it has the most rollouts
+        assert!(ranking[0].alive);
+        assert!(ranking[1..].iter().all(|s| !s.alive));
+        assert!(ranking.iter().all(|s| s.rollouts <= ranking[0].rollouts));
+        assert!(ranking.iter().any(|s| s.rollouts < ranking[0].rollouts));
+        for s in &ranking {
+            assert!(s.interval.0 <= s.value && s.value <= s.interval.1);
+        }
+
+        // the same seed gives the same ranking
+        let again = simulate(&board, rack, &config)?;
+        let values = |ranking: &[SimScore]| -> Vec<(String, String, f32)> {
+            ranking
+                .iter()
+                .map(|s| (s.score.word.clone(), s.score.played.clone(), s.value))
+                .collect()
+        };
+        assert_eq!(values(&ranking), values(&again));
+        Ok(())
+    }
+
+    #[test]
+    fn test_anytime() -> Result<()> {
+        let board = board()?;
+        let rack: Letters = board.encode("tesnore")?;
+        let config = SearchConfig {
+            candidates: 8,
+            samples: 2,
+            swaps: false,
+            ..SearchConfig::default()
+        };
+        let mut simulation = Simulation::new(&board, rack, &config)?;
+        assert!(simulation.ranking().iter().all(|s| s.rollouts == 0));
+        assert!(simulation.step()?);
+        assert_eq!(simulation.rounds(), 1);
+        let ranking = simulation.ranking();
+        assert_eq!(ranking.iter().filter(|s| s.alive).count(), 4);
+        assert!(ranking.iter().all(|s| s.rollouts == 2));
+
+        // without time only the first rollout of each candidate is run
+        let config = SearchConfig {
+            time_budget: Some(Duration::from_secs(0)),
+            ..config
+        };
+        let ranking = simulate(&board, rack, &config)?;
+        assert!(ranking.iter().all(|s| s.rollouts == 1));
+        Ok(())
+    }
+
+    #[test]
+    fn test_rollout() -> Result<()> {
+        // the opponent move is undone without the move history
+        let mut board = board()?.with_history_limit(0);
+        let state = board.horizontal();
+        let (opp, next) = (board.encode("e")?, board.encode("z")?);
+        let (opp_score, next_score) = rollout(&mut board, opp, next)?;
+        assert_eq!(opp_score, best_score(&board, opp)?);
+        assert_eq!(board.horizontal(), state);
+        assert!(board.redo().is_none());
+        // our reply is calculated on the board with the opponent move:
+        // "z" can not be played on the board with only "rust"
+        assert_eq!(best_score(&board, next)?, 0);
+        assert!(next_score > 0);
+        Ok(())
+    }
+}
@@ -1,24 +1,34 @@
+use crate::breakdown::{ScoreBreakdown, TilePoints, WordPoints};
+use crate::game::{BINGO_BONUS, RACK_SIZE};
 use crate::grid::{
     Cell::{LetterBonus, WordBonus},
     Grid,
 };
+use crate::placement::{MoveRecord, Placement, Play};
 use crate::tiles::TryIntoLetters;
 use crate::tilesets::{Language, TileSet};
-use crate::wordlist::{LetterSet, RowData, Wordlist};
-use crate::{Cell, Codec, Error, Item, ItemList, Letter, Letters, List, Row, Tile, Word};
+use crate::wordlist::{Gaddag, LetterSet, MoveGenerator, RowData, Wordlist};
+use crate::{Cell, Codec, Error, Item, ItemList, Label, Letter, Letters, List, Row, Tile, Word};
 
 #[cfg(feature = "flame_it")]
 use flamer::flame;
 #[cfg(feature = "rayon")]
 use rayon::prelude::*;
-use std::cmp::Reverse;
+use std::cmp::{Ordering, Reverse};
 use std::fmt;
+use std::sync::Arc;
 
 const N: usize = 15;
+/// The default maximum number of moves in the move history
+const HISTORY_LIMIT: usize = 32;
 type State = [Row; N];
 
 /// Score returned by calc_all_word_scores
-#[derive(Debug, Clone, Copy)]
+///
+/// Scores are totally ordered: a score is greater than another if it has more points, or the same points
+/// and comes first by position (`y`, then `x`), direction (horizontal first) and word.
+/// Sorting with this order gives the same result regardless of the order in which the words were found.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Score {
     /// word start x: 0..N
     pub x: usize,
@@ -32,6 +42,29 @@ pub struct Score {
     pub score: u32,
 }
 
+impl Ord for Score {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.score
+            .cmp(&other.score)
+            .then_with(|| {
+                (other.y, other.x, !other.horizontal).cmp(&(self.y, self.x, !self.horizontal))
+            })
+            .then_with(|| {
+                other
+                    .word
+                    .iter()
+                    .map(Item::code)
+                    .cmp(self.word.iter().map(Item::code))
+            })
+    }
+}
+
+impl PartialOrd for Score {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
 /// Display the board state as 15 lines of 15 squares.
 /// Empty squares show as ".".
 impl<'a> fmt::Display for Board<'a> {
@@ -56,14 +89,39 @@ impl<'a> Default for Board<'a> {
 /// * A grid of 15x15 squares with possible letter/word bonus,
 /// * The tile distribution for language used (number of letters, and value of each letter),
 /// * The wordlist used for the game.
-#[derive(Debug, Clone)]
+///
+/// The wordlist (and the [`Gaddag`], if used) is shared: cloning a board does not copy it.
+/// A clone starts with an empty move history, see [`history`](Board::history).
+#[derive(Debug)]
 pub struct Board<'a> {
     board: Grid,
     horizontal: State,
     vertical: State,
     rowdata: [[RowData; N]; 2],
     tileset: TileSet<'a>,
-    wordlist: Wordlist,
+    wordlist: Arc<Wordlist>,
+    gaddag: Option<Arc<Gaddag>>,
+    history: Vec<MoveRecord>,
+    history_limit: usize,
+    undone: Vec<Placement>,
+}
+
+/// Clone the board without its move history, so clones in searches stay cheap.
+impl<'a> Clone for Board<'a> {
+    fn clone(&self) -> Self {
+        Board {
+            board: self.board.clone(),
+            horizontal: self.horizontal,
+            vertical: self.vertical,
+            rowdata: self.rowdata,
+            tileset: self.tileset.clone(),
+            wordlist: self.wordlist.clone(),
+            gaddag: self.gaddag.clone(),
+            history: Vec::new(),
+            history_limit: self.history_limit,
+            undone: Vec::new(),
+        }
+    }
 }
 
 impl<'a> Board<'a> {
@@ -85,6 +143,7 @@ impl<'a> Board<'a> {
     ///```
     /// Additional builder functions can be used to set the wordlist, grid and state of the board.
     /// See also:
+    /// - [`with_wordlist`](Board::with_wordlist)
     /// - [`with_wordlist_from_file`](Board::with_wordlist_from_file)
     /// - [`with_wordlist_from_words`](Board::with_wordlist_from_words)
     /// - [`with_state_from_strings`](Board::with_state_from_strings)
@@ -107,14 +166,48 @@ impl<'a> Board<'a> {
             vertical: [empty_row; N],
             rowdata: [[empty_rowdata; N], [empty_rowdata; N]],
             tileset,
-            wordlist,
+            wordlist: Arc::new(wordlist),
+            gaddag: None,
+            history: Vec::new(),
+            history_limit: HISTORY_LIMIT,
+            undone: Vec::new(),
         }
     }
 
     /// Set the wordlist for the board.
-    pub fn set_wordlist(&mut self, wordlist: Wordlist) {
-        self.wordlist = wordlist;
+    /// The wordlist can be a [`Wordlist`], or an `Arc<Wordlist>` that is shared with other boards.
+    /// This clears the move history.
+    pub fn set_wordlist<W: Into<Arc<Wordlist>>>(&mut self, wordlist: W) {
+        let wordlist = wordlist.into();
+        if !Arc::ptr_eq(&self.wordlist, &wordlist) {
+            self.wordlist = wordlist;
+            if self.gaddag.is_some() {
+                self.gaddag = Some(self.wordlist.gaddag());
+            }
+        }
         self.set_rowdata();
+        self.clear_history();
+    }
+
+    /// Specify the wordlist, and returns the modified board.
+    ///
+    /// Boards that are created with the same `Arc<Wordlist>` share one copy of the wordlist.
+    /// ## Examples
+    /// ```
+    /// # use wordfeud_solver::{Board, Codec, Error, Language, Wordlist};
+    /// use std::sync::Arc;
+    ///
+    /// let wordlist = Arc::new(Wordlist::from_words(&["rust", "rest"], &Codec::default())?);
+    /// let boards: Vec<Board> = (0..10)
+    ///     .map(|_| Board::new(Language::NL).with_wordlist(wordlist.clone()))
+    ///     .collect();
+    /// assert_eq!(Arc::strong_count(&wordlist), 11);
+    /// # Ok::<(), Error>(())
+    /// ```
+    #[must_use]
+    pub fn with_wordlist<W: Into<Arc<Wordlist>>>(mut self, wordlist: W) -> Board<'a> {
+        self.set_wordlist(wordlist);
+        self
     }
 
     /// Specify the wordlist by reading it from `wordfile`, and returns the modified board.
@@ -148,6 +241,18 @@ impl<'a> Board<'a> {
         Ok(self)
     }
 
+    #[cfg(feature = "mmap")]
+    /// Specify the wordlist by memory-mapping the binary `wordfile`, and returns the modified board.
+    ///
+    /// The `wordfile` should be written with [`Wordlist::write_mapped_file`].
+    ///
+    /// ## Errors
+    /// This function will give an error if the `wordfile` does not exist, or is not a valid wordlist file.
+    pub fn with_wordlist_from_mapped_file(mut self, wordfile: &str) -> Result<Board<'a>, Error> {
+        self.set_wordlist(Wordlist::from_mapped_file(wordfile, self.codec())?);
+        Ok(self)
+    }
+
     #[cfg(feature = "bincode")]
     /// Specify the wordlist by deserializing it from the `wordfile`, and returns the modified board.
     ///
@@ -226,6 +331,7 @@ impl<'a> Board<'a> {
     }
 
     /// Set board state from a list of rows.
+    /// This clears the move history.
     pub fn set_state(&mut self, rows: &State) {
         self.horizontal = *rows;
         for i in 0..N {
@@ -234,6 +340,7 @@ impl<'a> Board<'a> {
             }
         }
         self.set_rowdata();
+        self.clear_history();
     }
 
     /// Set board cells from string representation
@@ -252,11 +359,51 @@ impl<'a> Board<'a> {
         Ok(self)
     }
 
+    /// Set the move generator for the board.
+    /// The [`Gaddag`] is built once for each wordlist, see [`Wordlist::gaddag`]:
+    /// boards with the same `Arc<Wordlist>` share it.
+    pub fn set_move_generator(&mut self, generator: MoveGenerator) {
+        self.gaddag = match generator {
+            MoveGenerator::Trie => None,
+            MoveGenerator::Gaddag => Some(self.wordlist.gaddag()),
+        };
+    }
+
+    /// Set the move generator, and return the modified board.
+    /// Both generators give the same words, see [`MoveGenerator`].
+    /// ## Examples
+    /// ```
+    /// # use wordfeud_solver::{Board, Error, MoveGenerator};
+    /// let board = Board::default()
+    ///     .with_wordlist_from_words(&["the", "quick", "brown", "fox"])?
+    ///     .with_move_generator(MoveGenerator::Gaddag);
+    /// assert_eq!(board.calc_all_word_scores("befnrowx")?.len(), 16);
+    /// # Ok::<(), Error>(())
+    /// ```
+    #[must_use]
+    pub fn with_move_generator(mut self, generator: MoveGenerator) -> Board<'a> {
+        self.set_move_generator(generator);
+        self
+    }
+
+    /// Return the move generator of the board
+    pub fn move_generator(&self) -> MoveGenerator {
+        match self.gaddag {
+            Some(_) => MoveGenerator::Gaddag,
+            None => MoveGenerator::Trie,
+        }
+    }
+
     /// Return reference to our wordlist
     pub fn wordlist(&self) -> &Wordlist {
         &self.wordlist
     }
 
+    /// Return the shared handle to our wordlist, to use the same wordlist for other boards.
+    pub fn shared_wordlist(&self) -> &Arc<Wordlist> {
+        &self.wordlist
+    }
+
     /// Return the board horizontal state
     pub fn horizontal(&self) -> State {
         self.horizontal
@@ -273,7 +420,7 @@ impl<'a> Board<'a> {
     }
 
     /// Return tileset
-    pub fn tileset(&self) -> &'a TileSet {
+    pub fn tileset(&self) -> &'a TileSet<'_> {
         &self.tileset
     }
 
@@ -295,6 +442,13 @@ impl<'a> Board<'a> {
         }
     }
 
+    /// Check if there are no tiles on the board.
+    pub fn is_empty(&self) -> bool {
+        self.horizontal
+            .iter()
+            .all(|row| row.iter().all(Cell::is_empty))
+    }
+
     fn calc_rowdata(&self, horizontal: bool, i: usize) -> RowData {
         let sw = self.surrounding_words(horizontal, i);
         let labelsets = sw
@@ -307,6 +461,20 @@ impl<'a> Board<'a> {
         labelsets.zip(connected).collect()
     }
 
+    /// Calculate the rowdata for square `j` in row `i`: the legal characters and the connected flag.
+    fn calc_square_data(&self, horizontal: bool, i: usize, j: usize) -> (LetterSet, bool) {
+        let crossing_row = if horizontal {
+            self.vertical[j]
+        } else {
+            self.horizontal[j]
+        };
+        let surrounding = crossing_row.surrounding_word(i);
+        (
+            self.wordlist.get_legal_characters(&surrounding),
+            (i, j) == (7, 7) || !surrounding.is_empty_cell(),
+        )
+    }
+
     fn set_rowdata(&mut self) {
         for i in 0..N {
             self.rowdata[0][i] = self.calc_rowdata(false, i);
@@ -358,16 +526,301 @@ impl<'a> Board<'a> {
         Ok(self.decode(used_letters))
     }
 
+    /// Play the tiles in `placement` on the board.
+    /// The direction, the main word, the words in the crossing direction, the score and
+    /// the used letters are derived from the placed tiles.
+    /// Modifies the board state if `modify` is true.
+    /// <br>**NOTE**: the words are not checked with the wordlist, use [`validate_move`](Board::validate_move) for that.
+    /// ## Errors
+    /// - [`NoTilesPlaced`](Error::NoTilesPlaced) if `placement` is empty.
+    /// - [`TilePlacementError`](Error::TilePlacementError) if a tile is placed outside the board.
+    /// - [`TileReplaceError`](Error::TileReplaceError) if a tile is placed on an occupied square.
+    /// - [`NotContiguous`](Error::NotContiguous) if the tiles are not in one line, or do not form one word.
+    /// ## Examples
+    /// ```
+    /// # use wordfeud_solver::{Board, Error, Placement, Word};
+    /// let mut board = Board::default();
+    /// board.play_word("rust", 7, 7, true, true)?;
+    /// // place "e" and "t" below the "r" of "rust"
+    /// let tiles: Word = board.encode("et")?;
+    /// let placement = Placement::from(vec![(7, 8, tiles[0]), (7, 9, tiles[1])]);
+    /// let play = board.play_tiles(&placement, true)?;
+    /// assert_eq!((play.x, play.y, play.horizontal), (7, 7, false));
+    /// assert_eq!(board.decode(play.word), "ret");
+    /// assert_eq!(board.decode(play.used), "et");
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub fn play_tiles(&mut self, placement: &Placement, modify: bool) -> Result<Play, Error> {
+        if modify {
+            self.play(placement)
+        } else {
+            self.try_tiles(placement)
+        }
+    }
+
+    /// Play the tiles in `placement` on the board, and add the move to the move history.
+    /// The move can be reverted with [`undo`](Board::undo).
+    /// Playing a move discards the moves that can be replayed with [`redo`](Board::redo).
+    /// <br>**NOTE**: the words are not checked with the wordlist, use [`validate_move`](Board::validate_move) for that.
+    /// ## Errors
+    /// See [`play_tiles`](Board::play_tiles).
+    /// ## Examples
+    /// ```
+    /// # use wordfeud_solver::{Board, Error, Placement, Word};
+    /// let mut board = Board::default();
+    /// let tiles: Word = board.encode("rust")?;
+    /// let placement: Placement = (0..4).map(|i| (7 + i, 7, tiles[i])).collect::<Vec<_>>().into();
+    /// board.play(&placement)?;
+    /// assert_eq!(board.history().len(), 1);
+    /// assert_eq!(board.undo(), Some(placement));
+    /// assert!(board.is_empty());
+    /// let play = board.redo().unwrap();
+    /// assert_eq!(board.decode(play.word), "rust");
+    /// assert!(board.is_occupied(7, 7));
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub fn play(&mut self, placement: &Placement) -> Result<Play, Error> {
+        let (play, record) = self.place(placement)?;
+        self.undone.clear();
+        self.history.push(record);
+        self.trim_history();
+        Ok(play)
+    }
+
+    /// Revert the last move in the move history.
+    /// Returns the placed tiles of the move, or `None` if the history is empty.
+    /// The move can be replayed with [`redo`](Board::redo).
+    pub fn undo(&mut self) -> Option<Placement> {
+        let record = self.history.pop()?;
+        self.unplace(&record);
+        self.undone.push(record.placement.clone());
+        Some(record.placement)
+    }
+
+    /// Place the tiles in `placement` on the board, without changing the move history.
+    /// Returns the play, and the record to remove the tiles again with [`unplace`](Board::unplace).
+    ///
+    /// The search functions try moves with this, so the moves that can be undone and redone
+    /// are only the moves that were played with [`play`](Board::play).
+    pub(crate) fn place(&mut self, placement: &Placement) -> Result<(Play, MoveRecord), Error> {
+        let play = self.try_tiles(placement)?;
+        let record = self.place_tiles(placement.clone(), play.score);
+        Ok((play, record))
+    }
+
+    /// Remove the tiles of a move from the board, and restore the rowdata as it was before the move.
+    /// The move history is not changed.
+    pub(crate) fn unplace(&mut self, record: &MoveRecord) {
+        for &(x, y, _) in record.placement.iter() {
+            self.horizontal[y][x] = Cell::EMPTY;
+            self.vertical[x][y] = Cell::EMPTY;
+        }
+        for &(horizontal, i, rowdata) in &record.rowdata {
+            self.rowdata[horizontal as usize][i] = rowdata;
+        }
+    }
+
+    /// Replay the last move that was reverted with [`undo`](Board::undo).
+    /// Returns `None` if there is no move to replay.
+    pub fn redo(&mut self) -> Option<Play> {
+        let placement = self.undone.pop()?;
+        match self.try_tiles(&placement) {
+            Ok(play) => {
+                self.push_move(placement, play.score);
+                Some(play)
+            }
+            Err(_) => {
+                self.undone.push(placement);
+                None
+            }
+        }
+    }
+
+    /// Return the moves played on the board since the state was last set, oldest move first.
+    /// Only the last [`history_limit`](Board::history_limit) moves are kept.
+    /// The history is not cloned: a clone of the board starts with an empty history and nothing to redo.
+    pub fn history(&self) -> &[MoveRecord] {
+        &self.history
+    }
+
+    /// Return the maximum number of moves in the move history, 32 by default.
+    pub fn history_limit(&self) -> usize {
+        self.history_limit
+    }
+
+    /// Set the maximum number of moves in the move history.
+    /// When a move is played on a full history, the oldest move is dropped and can no longer be undone.
+    pub fn set_history_limit(&mut self, limit: usize) {
+        self.history_limit = limit;
+        self.trim_history();
+    }
+
+    /// Set the maximum number of moves in the move history, and return the modified board.
+    /// See [`set_history_limit`](Board::set_history_limit).
+    #[must_use]
+    pub fn with_history_limit(mut self, limit: usize) -> Board<'a> {
+        self.set_history_limit(limit);
+        self
+    }
+
+    fn trim_history(&mut self) {
+        let excess = self.history.len().saturating_sub(self.history_limit);
+        self.history.drain(..excess);
+    }
+
+    fn clear_history(&mut self) {
+        self.history.clear();
+        self.undone.clear();
+    }
+
+    /// Place the tiles on the board and push the move on the history.
+    fn push_move(&mut self, placement: Placement, score: u32) {
+        let record = self.place_tiles(placement, score);
+        self.history.push(record);
+        self.trim_history();
+    }
+
+    /// Place the tiles on the board, and return the record to remove them again.
+    /// Only the rowdata of the rows affected by the placed tiles is recalculated.
+    fn place_tiles(&mut self, placement: Placement, score: u32) -> MoveRecord {
+        for &(x, y, tile) in placement.iter() {
+            self.horizontal[y][x] = tile.into_cell();
+            self.vertical[x][y] = tile.into_cell();
+        }
+        let affected = self.affected_squares(&placement);
+        let mut rowdata = Vec::new();
+        for (horizontal, rows) in [false, true].iter().zip(&affected) {
+            for (i, squares) in rows.iter().enumerate() {
+                if squares.iter().any(|&flag| flag) {
+                    rowdata.push((*horizontal, i, self.rowdata[*horizontal as usize][i]));
+                }
+            }
+        }
+        for (horizontal, rows) in [false, true].iter().zip(&affected) {
+            for (i, squares) in rows.iter().enumerate() {
+                for (j, _) in squares.iter().enumerate().filter(|(_, &flag)| flag) {
+                    self.rowdata[*horizontal as usize][i][j] =
+                        self.calc_square_data(*horizontal, i, j);
+                }
+            }
+        }
+        MoveRecord {
+            placement,
+            score,
+            rowdata,
+        }
+    }
+
+    /// Returns the squares with rowdata that depends on the squares in `placement`,
+    /// as flags indexed by direction, row and square.
+    /// A tile changes the crossing word of every square in the word through the tile,
+    /// and of the squares directly before and after that word.
+    /// The rowdata of other squares in those rows does not change.
+    fn affected_squares(&self, placement: &Placement) -> [[[bool; N]; N]; 2] {
+        let mut affected = [[[false; N]; N]; 2];
+        for &(x, y, _) in placement.iter() {
+            let (s, e) = self.vertical[x].start_end(y);
+            for row in &mut affected[1][s.saturating_sub(1)..=e.min(N - 1)] {
+                row[x] = true;
+            }
+            let (s, e) = self.horizontal[y].start_end(x);
+            for row in &mut affected[0][s.saturating_sub(1)..=e.min(N - 1)] {
+                row[y] = true;
+            }
+        }
+        affected
+    }
+
+    fn try_tiles(&self, placement: &Placement) -> Result<Play, Error> {
+        let &(x0, y0, _) = placement.first().ok_or(Error::NoTilesPlaced)?;
+        for (k, &(x, y, _)) in placement.iter().enumerate() {
+            if x >= N || y >= N {
+                return Err(Error::TilePlacementError {
+                    x,
+                    y,
+                    horizontal: true,
+                    len: 1,
+                });
+            }
+            if self.is_occupied(x, y)
+                || placement[..k].iter().any(|&(px, py, _)| (px, py) == (x, y))
+            {
+                return Err(Error::TileReplaceError { x, y });
+            }
+        }
+        let horizontal = if placement.len() == 1 {
+            // a single tile is played horizontally, unless it only forms a vertical word
+            let (s, e) = self.horizontal[y0].start_end(x0);
+            let (vs, ve) = self.vertical[x0].start_end(y0);
+            e - s > 1 || ve - vs == 1
+        } else if let Some(&(x, y, _)) = placement.iter().find(|&&(_, y, _)| y != y0) {
+            if let Some(&(x, y, _)) = placement.iter().find(|&&(x, _, _)| x != x0) {
+                return Err(Error::NotContiguous { x, y });
+            }
+            debug_assert!(x == x0 && y != y0);
+            false
+        } else {
+            true
+        };
+
+        // the main word is the run of tiles through the placed tiles
+        let i = if horizontal { y0 } else { x0 };
+        let mut row = if horizontal {
+            self.horizontal[i]
+        } else {
+            self.vertical[i]
+        };
+        for &(x, y, tile) in placement.iter() {
+            row[if horizontal { x } else { y }] = tile.into_cell();
+        }
+        let along = |&(x, y, _): &(usize, usize, Tile)| if horizontal { x } else { y };
+        let first = placement.iter().map(along).min().unwrap_or(0);
+        let last = placement.iter().map(along).max().unwrap_or(0);
+        let (s, e) = row.start_end(first);
+        if e <= last {
+            let (x, y) = if horizontal { (e, i) } else { (i, e) };
+            return Err(Error::NotContiguous { x, y });
+        }
+        let word: Word = row[s..e].iter().filter_map(Cell::tile).collect();
+        let (x, y) = if horizontal { (s, i) } else { (i, s) };
+        let used = self.try_word(word, x, y, horizontal)?;
+        let score = self.calc_word_points_unchecked(&word, x, y, horizontal, true);
+        let cross_words = placement
+            .iter()
+            .filter_map(|&(x, y, tile)| self.cross_word(tile, x, y, horizontal))
+            .collect();
+        Ok(Play {
+            x,
+            y,
+            horizontal,
+            word,
+            cross_words,
+            score,
+            used,
+        })
+    }
+
     fn play_word_unchecked(&mut self, word: Word, x: usize, y: usize, horizontal: bool) {
-        let mut x = x;
-        let mut y = y;
+        let placement = self.word_placement(word, x, y, horizontal);
+        if !placement.is_empty() {
+            let score = self.calc_word_points_unchecked(&word, x, y, horizontal, true);
+            self.undone.clear();
+            self.push_move(placement, score);
+        }
+    }
+
+    /// Returns the tiles of `word` at `x`, `y` in direction `horizontal` that are not yet on the board.
+    /// Tiles outside the board are included, and rejected when the placement is played.
+    pub fn word_placement(&self, word: Word, x: usize, y: usize, horizontal: bool) -> Placement {
         let (dx, dy) = if horizontal { (1, 0) } else { (0, 1) };
-        for tile in word {
-            self.horizontal[y][x] = tile.into_cell();
-            x += dx;
-            y += dy;
+        let mut placement = Placement::new();
+        for (k, tile) in word.into_iter().enumerate() {
+            let (x, y) = (x + k * dx, y + k * dy);
+            if !self.is_occupied(x, y) {
+                placement.push(x, y, tile);
+            }
         }
-        self.set_state(&self.horizontal.clone());
+        placement
     }
 
     fn try_word(&self, word: Word, x: usize, y: usize, horizontal: bool) -> Result<Letters, Error> {
@@ -397,6 +850,124 @@ impl<'a> Board<'a> {
         Ok(used_letters)
     }
 
+    /// Check if playing `word` at `x`, `y` in direction `horizontal` with the letters in `rack` is a legal move.
+    ///
+    /// A legal move must:
+    /// - place at least one tile on the board,
+    /// - not be extended by tiles directly before or after the word,
+    /// - form a word that is in the wordlist,
+    /// - only form words in the crossing direction that are in the wordlist,
+    /// - use the start square if it is the first move, or else connect to the tiles on the board,
+    /// - only use letters from `rack`.
+    /// ## Errors
+    /// - If `word` or `rack` cannot be encoded.
+    /// - If the placed `word` does not fit on the board, or replaces a tile on the board.
+    /// - [`InvalidMove`](Error::InvalidMove) with a list of all violated rules otherwise.
+    /// ## Examples
+    /// ```
+    /// # use wordfeud_solver::{Board, Error};
+    /// let board = Board::default().with_wordlist_from_words(&["rust", "rest"])?;
+    /// assert!(board.validate_move("rust", 7, 7, true, "rstu").is_ok());
+    /// // The first word must use the start square
+    /// assert!(board.validate_move("rust", 0, 0, true, "rstu").is_err());
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub fn validate_move<T: TryIntoLetters>(
+        &self,
+        word: &str,
+        x: usize,
+        y: usize,
+        horizontal: bool,
+        rack: T,
+    ) -> Result<(), Error> {
+        let word = self.encode(word)?;
+        let rack = rack.try_into_letters(self.codec())?;
+        let violations = self.move_violations(word, x, y, horizontal, rack)?;
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(Error::InvalidMove(violations))
+        }
+    }
+
+    /// Returns the list of rules that are violated by playing `word` at `x0`, `y0` with `rack`.
+    /// See [`validate_move`](Board::validate_move).
+    pub(crate) fn move_violations(
+        &self,
+        word: Word,
+        x0: usize,
+        y0: usize,
+        horizontal: bool,
+        rack: Letters,
+    ) -> Result<Vec<Error>, Error> {
+        let used_letters = self.try_word(word, x0, y0, horizontal)?;
+        let mut violations = Vec::new();
+        let len = word.len();
+        let (dx, dy) = if horizontal { (1, 0) } else { (0, 1) };
+        if used_letters.is_empty() {
+            violations.push(Error::NoTilesPlaced);
+        }
+
+        // the squares directly before and after the word must be empty
+        if x0 >= dx && y0 >= dy && self.is_occupied(x0 - dx, y0 - dy) {
+            violations.push(Error::NotContiguous {
+                x: x0 - dx,
+                y: y0 - dy,
+            });
+        }
+        let (xe, ye) = (x0 + len * dx, y0 + len * dy);
+        if self.is_occupied(xe, ye) {
+            violations.push(Error::NotContiguous { x: xe, y: ye });
+        }
+
+        let labels: Vec<Label> = word.iter().map(Tile::label).collect();
+        if !self.wordlist.is_word(&labels) {
+            violations.push(Error::WordNotInWordlist(self.decode(word)));
+        }
+
+        // check the crossing words, and if the word is connected
+        let (i, j0) = if horizontal { (y0, x0) } else { (x0, y0) };
+        let surrounding = self.surrounding_words(horizontal, i);
+        let rowdata = &self.rowdata[horizontal as usize][i];
+        let mut connected = false;
+        for (k, tile) in word.into_iter().enumerate() {
+            let j = j0 + k;
+            let (x, y) = if horizontal { (j, i) } else { (i, j) };
+            if self.is_occupied(x, y) {
+                connected = true;
+                continue;
+            }
+            connected |= rowdata[j].1;
+            let sw = surrounding[j];
+            if !sw.is_empty_cell() {
+                let cross: Vec<Label> = sw
+                    .iter()
+                    .map(|cell| cell.tile().map_or(tile.label(), |t| t.label()))
+                    .collect();
+                if !self.wordlist.is_word(&cross) {
+                    violations.push(Error::InvalidCrossWord {
+                        word: self.codec().decode(&cross).join(""),
+                        x,
+                        y,
+                    });
+                }
+            }
+        }
+        if self.is_empty() {
+            if !(i == N / 2 && j0 <= N / 2 && N / 2 < j0 + len) {
+                violations.push(Error::StartSquareNotUsed);
+            }
+        } else if !connected {
+            violations.push(Error::NotConnected);
+        }
+
+        // the used letters must be in the rack
+        if let Err(missing) = rack.remove_letters(used_letters) {
+            violations.push(Error::LettersNotInRack(self.decode(missing)));
+        }
+        Ok(violations)
+    }
+
     /// Returns the the surrounding characters that would need to form a valid
     /// word in order to fill each position in the i'the row of the board
     #[cfg_attr(feature = "flame_it", flame)]
@@ -434,7 +1005,7 @@ impl<'a> Board<'a> {
     /// In this example, the values of the letters are: `w`:4 `o`:1, `r`:1, `d`:2, `f`:4, `u`:2.
     /// The `f` is on 2x word bonus, and the last `d` is on 2x letter bonus. The total value of the
     /// word is `2 x (4 + 1 + 1 + 2 + 4 + 1 +2 + (2 x 2)) = 2 x 19 = 38`. Because all 7 letters are played we get an extra
-    /// "bingo" bonus of 40 points.  
+    /// "bingo" bonus of [`BINGO_BONUS`] points.
     pub fn calc_word_points(
         &self,
         word: &Word,
@@ -487,17 +1058,7 @@ impl<'a> Board<'a> {
                     _ => {}
                 }
                 if include_crossing_words {
-                    let (crow, ci) = if horizontal {
-                        (self.vertical[x], y)
-                    } else {
-                        (self.horizontal[y], x)
-                    };
-                    let row = crow;
-                    let (s, e) = row.start_end(ci);
-                    if e - s > 1 {
-                        let (cx, cy) = if horizontal { (x, s) } else { (s, y) };
-                        let cword =
-                            Word::from(&row.replace(s, e, Cell::EMPTY, Cell::from_tile(tile)));
+                    if let Some((cx, cy, cword)) = self.cross_word(tile, x, y, horizontal) {
                         total_points +=
                             self.calc_word_points_unchecked(&cword, cx, cy, !horizontal, false);
                     }
@@ -508,12 +1069,152 @@ impl<'a> Board<'a> {
             y += dy;
         }
         total_points += word_points * word_multiplicator;
-        if tiles_used >= 7 {
-            total_points += 40; // Bingo bonus
+        if tiles_used >= RACK_SIZE {
+            total_points += BINGO_BONUS;
         }
         total_points
     }
 
+    /// Calculate a detailed score of `word` placed at `x`, `y`, `horizontal`, that has not yet
+    /// been played on the board.
+    /// The [`ScoreBreakdown`](crate::ScoreBreakdown) lists the points for the main word and each
+    /// crossing word, the activated bonus squares and the bingo bonus.
+    /// The total is the same as returned by [`calc_word_points`](Board::calc_word_points).
+    /// ## Errors
+    /// - If the placed `word` would cross the right or bottom boarder.
+    /// ## Examples
+    /// ```
+    /// # use wordfeud_solver::{Board, Error};
+    /// let board = Board::default();
+    /// let word = board.encode("wordfeud")?;
+    /// let breakdown = board.score_breakdown(&word, 7, 7, true)?;
+    /// assert_eq!(breakdown.main_word.word_multiplier, 2);
+    /// assert_eq!(breakdown.main_word.points, 38);
+    /// assert!(breakdown.bingo);
+    /// assert_eq!(breakdown.total, 78);
+    /// # Ok::<(), Error>(())
+    /// ```
+    /// To get the breakdown of a [`Score`] returned by [`calc_all_word_scores`](Board::calc_all_word_scores)
+    /// use `board.score_breakdown(&s.word, s.x, s.y, s.horizontal)`.
+    pub fn score_breakdown(
+        &self,
+        word: &Word,
+        x: usize,
+        y: usize,
+        horizontal: bool,
+    ) -> Result<ScoreBreakdown, Error> {
+        let (dx, dy) = if horizontal { (1, 0) } else { (0, 1) };
+        let len = word.len();
+        if (x + len * dx > N) || (y + len * dy > N) {
+            return Err(Error::TilePlacementError {
+                x,
+                y,
+                horizontal,
+                len,
+            });
+        }
+        let main_word = self.word_points(word, x, y, horizontal);
+        let mut cross_words = Vec::new();
+        let mut tiles_used = 0;
+        for tp in &main_word.tiles {
+            if !self.is_occupied(tp.x, tp.y) {
+                tiles_used += 1;
+                if let Some((cx, cy, cword)) = self.cross_word(tp.tile, tp.x, tp.y, horizontal) {
+                    cross_words.push(self.word_points(&cword, cx, cy, !horizontal));
+                }
+            }
+        }
+        let bingo = tiles_used >= RACK_SIZE;
+        let bingo_bonus = if bingo { BINGO_BONUS } else { 0 };
+        let total =
+            main_word.points + cross_words.iter().map(|w| w.points).sum::<u32>() + bingo_bonus;
+        Ok(ScoreBreakdown {
+            main_word,
+            cross_words,
+            bingo,
+            bingo_bonus,
+            total,
+        })
+    }
+
+    /// Calculate a detailed score for the tiles in `placement`.
+    /// See [`score_breakdown`](Board::score_breakdown) and [`play_tiles`](Board::play_tiles).
+    /// ## Errors
+    /// If the tiles can not be placed on the board, see [`play_tiles`](Board::play_tiles).
+    pub fn placement_breakdown(&self, placement: &Placement) -> Result<ScoreBreakdown, Error> {
+        let play = self.try_tiles(placement)?;
+        self.score_breakdown(&play.word, play.x, play.y, play.horizontal)
+    }
+
+    /// Calculate the points for `word` placed at `x0`, `y0`, without crossing words and bingo bonus.
+    fn word_points(&self, word: &Word, x0: usize, y0: usize, horizontal: bool) -> WordPoints {
+        let (dx, dy) = if horizontal { (1, 0) } else { (0, 1) };
+        let mut word_multiplier = 1;
+        let mut tiles = Vec::new();
+        for (k, tile) in word.into_iter().enumerate() {
+            let (x, y) = (x0 + k * dx, y0 + k * dy);
+            let letter_points = self.tileset.points(tile.code());
+            let mut points = letter_points;
+            let mut bonus = None;
+            if !self.is_occupied(x, y) {
+                match self.board[y][x] {
+                    LetterBonus(n) => {
+                        points *= n;
+                        bonus = Some(LetterBonus(n));
+                    }
+                    WordBonus(n) => {
+                        word_multiplier *= n;
+                        bonus = Some(WordBonus(n));
+                    }
+                    _ => {}
+                }
+            }
+            tiles.push(TilePoints {
+                x,
+                y,
+                tile,
+                letter_points,
+                points,
+                bonus,
+            });
+        }
+        let points = tiles.iter().map(|tp| tp.points).sum::<u32>() * word_multiplier;
+        WordPoints {
+            x: x0,
+            y: y0,
+            horizontal,
+            word: *word,
+            tiles,
+            word_multiplier,
+            points,
+        }
+    }
+
+    /// Returns the word formed in the crossing direction when `tile` is placed at `x`, `y`
+    /// as part of a word in direction `horizontal`, as (`x`, `y`, `word`) tuple.
+    /// Returns None if `tile` does not touch other tiles in the crossing direction.
+    fn cross_word(
+        &self,
+        tile: Tile,
+        x: usize,
+        y: usize,
+        horizontal: bool,
+    ) -> Option<(usize, usize, Word)> {
+        let (row, i) = if horizontal {
+            (self.vertical[x], y)
+        } else {
+            (self.horizontal[y], x)
+        };
+        let (s, e) = row.start_end(i);
+        if e - s > 1 {
+            let (cx, cy) = if horizontal { (x, s) } else { (s, y) };
+            let cword = Word::from(&row.replace(s, e, Cell::EMPTY, Cell::from_tile(tile)));
+            Some((cx, cy, cword))
+        } else {
+            None
+        }
+    }
+
     /// Returns a list with (`pos`, `word`) tuples for all words that can be played on `row`
     /// with index `i`, in direction `horizontal`, given `letters`.
     /// In the returned tuples, `pos` is the start index of the `word` in `row`.
@@ -538,9 +1239,10 @@ impl<'a> Board<'a> {
         letters: Letters,
     ) -> Vec<(usize, Word)> {
         let rowdata = self.rowdata[horizontal as usize][i];
-        self.wordlist
-            .words(&row, &rowdata, &letters, None)
-            .collect()
+        match &self.gaddag {
+            Some(gaddag) => gaddag.words(row, &rowdata, &letters, None).collect(),
+            None => self.wordlist.words(row, &rowdata, &letters, None).collect(),
+        }
     }
 
     /// Calculate the score for each word that can be played on the board with `letters`.
@@ -556,7 +1258,7 @@ impl<'a> Board<'a> {
     /// In this example 16 results are returned: 8 in horizontal and 8 in vertical direction.
     /// See also [`Board::words`](Board::words).
     pub fn calc_all_word_scores<T: TryIntoLetters>(&self, letters: T) -> Result<Vec<Score>, Error> {
-        let letters = letters.try_into_letters(&self.codec())?;
+        let letters = letters.try_into_letters(self.codec())?;
         self.calc_all_word_scores_inner(letters)
     }
 
@@ -593,8 +1295,8 @@ impl<'a> Board<'a> {
             scores
         };
         {
-            scores.extend(self.horizontal.iter().enumerate().map(hor_scores).flatten());
-            scores.extend(self.vertical.iter().enumerate().map(ver_scores).flatten());
+            scores.extend(self.horizontal.iter().enumerate().flat_map(hor_scores));
+            scores.extend(self.vertical.iter().enumerate().flat_map(ver_scores));
         }
         Ok(scores)
     }
@@ -608,7 +1310,7 @@ impl<'a> Board<'a> {
     }
 
     pub fn codec(&self) -> &Codec {
-        &self.tileset.codec()
+        self.tileset.codec()
     }
 
     /// Encode string to Word with our codec
@@ -695,6 +1397,7 @@ impl<'a> Board<'a> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
     use std::time::Instant;
 
     type Result<T> = std::result::Result<T, Error>;
@@ -723,7 +1426,7 @@ mod tests {
 
     #[test]
     fn test_state() -> Result<()> {
-        let mut board = board_nl().with_state_from_strings(&TEST_STATE)?;
+        let mut board = board_nl().with_state_from_strings(TEST_STATE)?;
 
         assert!(board.is_occupied(4, 0));
         assert!(!board.is_occupied(0, 0));
@@ -736,7 +1439,7 @@ mod tests {
 
     #[test]
     fn test_surrounding_words() -> Result<()> {
-        let board = board_nl().with_state_from_strings(&TEST_STATE)?;
+        let board = board_nl().with_state_from_strings(TEST_STATE)?;
         let sw = board
             .surrounding_words(true, 8)
             .iter()
@@ -753,7 +1456,7 @@ mod tests {
 
     #[test]
     fn test_calc_word_points() -> Result<()> {
-        let board = board_nl().with_state_from_strings(&TEST_STATE)?;
+        let board = board_nl().with_state_from_strings(TEST_STATE)?;
         let word = board.encode("ster")?;
         let points = board.calc_word_points(&word, 3, 0, true, true)?;
         assert_eq!(7, points);
@@ -768,7 +1471,7 @@ mod tests {
         let words = &["af", "ja"];
         let board = board_nl()
             .with_wordlist_from_words(words)?
-            .with_state_from_strings(&TEST_STATE)?;
+            .with_state_from_strings(TEST_STATE)?;
         let letters: Letters = board.encode("j*")?;
         let i = 0;
         let horizontal = true;
@@ -788,7 +1491,7 @@ mod tests {
         ];
         let board = board_nl()
             .with_wordlist_from_words(words)?
-            .with_state_from_strings(&TEST_STATE)?;
+            .with_state_from_strings(TEST_STATE)?;
 
         let letters = "abel";
         let res = board.calc_all_word_scores(letters)?;
@@ -816,9 +1519,47 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_score_order() -> Result<()> {
+        let words = &[
+            "af", "ah", "al", "aar", "aas", "be", "bi", "bo", "bar", "bes", "bel",
+        ];
+        let board = board_nl()
+            .with_wordlist_from_words(words)?
+            .with_state_from_strings(TEST_STATE)?;
+        let mut res = board.calc_all_word_scores("abel")?;
+        res.sort_by(|a, b| b.cmp(a));
+        let best: Vec<(usize, usize, String)> = res
+            .iter()
+            .take(3)
+            .map(|s| (s.x, s.y, board.decode(s.word)))
+            .collect();
+        // "bel" and "bar" both have 14 points, "bel" comes first by position
+        assert_eq!(
+            best,
+            [
+                (3, 1, String::from("bel")),
+                (2, 2, String::from("bar")),
+                (13, 1, String::from("bo"))
+            ]
+        );
+
+        // the order does not depend on the order in which the words were found
+        let mut reversed: Vec<Score> = res.iter().rev().cloned().collect();
+        reversed.sort_by(|a, b| b.cmp(a));
+        assert_eq!(reversed, res);
+        for seed in 0..10 {
+            let mut shuffled = res.clone();
+            shuffled.shuffle(&mut StdRng::seed_from_u64(seed));
+            shuffled.sort_by(|a, b| b.cmp(a));
+            assert_eq!(shuffled, res);
+        }
+        Ok(())
+    }
+
     #[test]
     fn test_board() {
-        let board = board_nl().with_state_from_strings(&TEST_STATE).unwrap();
+        let board = board_nl().with_state_from_strings(TEST_STATE).unwrap();
         println!("{}", board);
     }
 
@@ -868,12 +1609,283 @@ mod tests {
         board.play_word("rust", 12, 7, true, true).unwrap();
     }
 
+    #[test]
+    fn test_validate_move() -> Result<()> {
+        let mut board = Board::default().with_wordlist_from_words(&["rust", "rest", "st", "at"])?;
+        assert!(board.validate_move("rust", 7, 7, true, "rstu").is_ok());
+        assert!(board.validate_move("rust", 4, 7, true, "rstu").is_ok());
+        let err = board.validate_move("rust", 0, 0, true, "rst").unwrap_err();
+        assert!(matches!(err, Error::InvalidMove(ref v) if matches!(v[..],
+            [Error::StartSquareNotUsed, Error::LettersNotInRack(ref s)] if s == "u")));
+
+        board.play_word("rust", 7, 7, true, true)?;
+        // "rest" down from the "r" in "rust"
+        assert!(board.validate_move("rest", 7, 7, false, "est").is_ok());
+        assert!(board.validate_move("rest", 7, 7, false, "e**").is_err());
+        assert!(board.validate_move("reST", 7, 7, false, "e**").is_ok());
+        // "rest" is not connected
+        let err = board.validate_move("rest", 0, 0, true, "rest").unwrap_err();
+        assert!(matches!(err, Error::InvalidMove(ref v) if matches!(v[..], [Error::NotConnected])));
+        // "arts" is not a word, and forms "sa" and "tr" below "rust"
+        let err = board.validate_move("arts", 9, 8, true, "arts").unwrap_err();
+        assert!(matches!(err, Error::InvalidMove(ref v) if matches!(v[..],
+            [Error::WordNotInWordlist(_),
+            Error::InvalidCrossWord { x: 9, y: 8, .. },
+            Error::InvalidCrossWord { x: 10, y: 8, .. }])));
+        // "st" is extended by the "r" in "rust"
+        let err = board.validate_move("st", 5, 7, true, "st").unwrap_err();
+        assert!(matches!(err, Error::InvalidMove(ref v) if matches!(v[..],
+            [Error::NotContiguous { x: 7, y: 7 }, Error::NotConnected])));
+        // no tiles are placed
+        let err = board.validate_move("rust", 7, 7, true, "").unwrap_err();
+        assert!(
+            matches!(err, Error::InvalidMove(ref v) if matches!(v[..], [Error::NoTilesPlaced]))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_play_tiles() -> Result<()> {
+        let mut board = board_nl().with_state_from_strings(TEST_STATE)?;
+        // "abel" at 3, 6 vertical, placed as single tiles
+        let tiles: Word = board.encode("abel")?;
+        let placement: Placement = (0..4)
+            .map(|k| (3, 6 + k, tiles[k]))
+            .collect::<Vec<_>>()
+            .into();
+        let play = board.play_tiles(&placement, false)?;
+        assert_eq!((play.x, play.y, play.horizontal), (3, 6, false));
+        assert_eq!(board.decode(play.word), "abel");
+        assert_eq!(
+            play.score,
+            board.calc_word_points(&tiles, 3, 6, false, true)?
+        );
+        let cross_words: Vec<String> = play
+            .cross_words
+            .iter()
+            .map(|&(_, _, word)| board.decode(word))
+            .collect();
+        assert_eq!(cross_words, ["aqua"]);
+
+        // a single tile that extends "ster" to the left
+        let tiles: Word = board.encode("s")?;
+        let placement = Placement::from(vec![(3, 0, tiles[0])]);
+        let play = board.play_tiles(&placement, true)?;
+        assert_eq!((play.x, play.y, play.horizontal), (3, 0, true));
+        assert_eq!(board.decode(play.word), "st");
+        assert!(play.cross_words.is_empty());
+        assert_eq!(board.decode(board.horizontal[0]), "...st.....c...f");
+        Ok(())
+    }
+
+    #[test]
+    fn test_shared_wordlist() -> Result<()> {
+        let board = board_nl()
+            .with_wordlist_from_words(&["rust", "rest"])?
+            .with_move_generator(MoveGenerator::Gaddag);
+        let mut other = board.clone();
+        assert!(Arc::ptr_eq(
+            board.shared_wordlist(),
+            other.shared_wordlist()
+        ));
+        // setting the same wordlist keeps the gaddag
+        other.set_wordlist(board.shared_wordlist().clone());
+        let gaddag = |board: &Board| board.gaddag.clone().unwrap();
+        assert!(Arc::ptr_eq(&gaddag(&board), &gaddag(&other)));
+        assert_eq!(Arc::strong_count(board.shared_wordlist()), 2);
+
+        let other = board_nl().with_wordlist(board.shared_wordlist().clone());
+        assert_eq!(Arc::strong_count(board.shared_wordlist()), 3);
+        assert_eq!(
+            other.calc_all_word_scores("rust")?.len(),
+            board.calc_all_word_scores("rust")?.len()
+        );
+        // boards created with the same wordlist share the gaddag
+        let other = other.with_move_generator(MoveGenerator::Gaddag);
+        assert!(Arc::ptr_eq(&gaddag(&board), &gaddag(&other)));
+        let mut other = board_nl().with_move_generator(MoveGenerator::Gaddag);
+        other.set_wordlist(board.shared_wordlist().clone());
+        assert!(Arc::ptr_eq(&gaddag(&board), &gaddag(&other)));
+        Ok(())
+    }
+
+    #[test]
+    fn test_undo_redo() -> Result<()> {
+        let words = &[
+            "af", "ah", "al", "aar", "aas", "be", "bi", "bo", "bar", "bes", "bel",
+        ];
+        let mut board = board_nl()
+            .with_wordlist_from_words(words)?
+            .with_state_from_strings(TEST_STATE)?;
+        let initial = board.clone();
+        let moves = board.calc_all_word_scores("abel")?;
+        for s in &moves {
+            board.play_word(&board.decode(s.word), s.x, s.y, s.horizontal, true)?;
+            assert_eq!(board.history().last().map(|r| r.score), Some(s.score));
+            // rowdata after a move is the same as when recalculated from scratch
+            let mut expect = board.clone();
+            expect.set_state(&board.horizontal);
+            assert_eq!(board.rowdata, expect.rowdata);
+            board.undo();
+            assert_eq!(board.horizontal, initial.horizontal);
+            assert_eq!(board.vertical, initial.vertical);
+            assert_eq!(board.rowdata, initial.rowdata);
+        }
+
+        // undo and redo a sequence of moves
+        for s in moves.iter().take(3) {
+            board.play_word(&board.decode(s.word), s.x, s.y, s.horizontal, true)?;
+        }
+        let played = board.clone();
+        assert_eq!(board.history().len(), 3);
+        while board.undo().is_some() {}
+        assert!(board.history().is_empty());
+        assert_eq!(board.rowdata, initial.rowdata);
+        while board.redo().is_some() {}
+        assert_eq!(board.history().len(), 3);
+        assert_eq!(board.horizontal, played.horizontal);
+        assert_eq!(board.rowdata, played.rowdata);
+
+        // a new move discards the undone moves
+        board.undo();
+        let s = moves[4];
+        board.play_word(&board.decode(s.word), s.x, s.y, s.horizontal, true)?;
+        assert!(board.redo().is_none());
+
+        // a move that can not be replayed stays available for redo
+        let undone = board.undo().unwrap();
+        board.play_tiles(&undone, true)?;
+        board.undone.push(undone);
+        assert!(board.redo().is_none());
+        assert_eq!(board.undone.len(), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_history_limit() -> Result<()> {
+        let mut board = board_nl()
+            .with_wordlist_from_words(&["af", "ah", "al"])?
+            .with_history_limit(2);
+        board.play_word("af", 7, 7, true, true)?;
+        board.play_word("ah", 7, 7, false, true)?;
+        board.play_word("al", 9, 6, false, true)?;
+        assert_eq!(board.history().len(), 2);
+        assert!(board.undo().is_some());
+        assert!(board.undo().is_some());
+        assert!(board.undo().is_none());
+        assert!(board.is_occupied(7, 7));
+        board.set_history_limit(0);
+        board.play_word("ah", 7, 7, false, true)?;
+        assert!(board.history().is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_clone_without_history() -> Result<()> {
+        let mut board = board_nl().with_wordlist_from_words(&["af", "ah"])?;
+        board.play_word("af", 7, 7, true, true)?;
+        board.play_word("ah", 7, 7, false, true)?;
+        board.undo();
+        let mut clone = board.clone();
+        assert_eq!(clone.horizontal, board.horizontal);
+        assert_eq!(clone.history_limit(), board.history_limit());
+        assert!(clone.history().is_empty());
+        assert!(clone.undo().is_none());
+        assert!(clone.redo().is_none());
+        // the original keeps its history
+        assert_eq!(board.history().len(), 1);
+        assert!(board.redo().is_some());
+        Ok(())
+    }
+
+    #[test]
+    fn test_place() -> Result<()> {
+        let mut board = board_nl()
+            .with_wordlist_from_words(&["af", "ah", "al"])?
+            .with_history_limit(1);
+        board.play_word("af", 7, 7, true, true)?;
+        board.undo();
+        board.play_word("ah", 7, 7, false, true)?;
+        board.undo();
+        let initial = board.clone();
+        let undone = board.undone.clone();
+        // placing and unplacing a move keeps the history and the moves to redo
+        for limit in &[1, 0] {
+            board.set_history_limit(*limit);
+            let word: Word = board.encode("al")?;
+            let placement = board.word_placement(word, 7, 7, true);
+            let (play, record) = board.place(&placement)?;
+            assert_eq!(board.decode(play.used), "al");
+            assert!(board.is_occupied(7, 7));
+            board.unplace(&record);
+            assert_eq!(board.horizontal, initial.horizontal);
+            assert_eq!(board.rowdata, initial.rowdata);
+            assert!(board.history().is_empty());
+            assert_eq!(board.undone, undone);
+        }
+        assert!(board.redo().is_some());
+        Ok(())
+    }
+
+    #[test]
+    fn test_score_breakdown() -> Result<()> {
+        let board = board_nl().with_state_from_strings(TEST_STATE)?;
+        let word = board.encode("abel")?;
+        let breakdown = board.score_breakdown(&word, 3, 6, false)?;
+        assert_eq!(breakdown.total, 32);
+        assert_eq!(breakdown.main_word.points, 18);
+        assert_eq!(breakdown.main_word.word_multiplier, 2);
+        assert_eq!(breakdown.cross_words.len(), 1);
+        let aqua = &breakdown.cross_words[0];
+        assert_eq!(board.decode(aqua.word), "aqua");
+        assert_eq!(aqua.points, 14);
+        assert!(!breakdown.bingo);
+
+        // the total matches the score for all possible words
+        let board =
+            board.with_wordlist_from_words(&["af", "ah", "al", "bar", "bes", "bel", "abel"])?;
+        for s in board.calc_all_word_scores("abel*")? {
+            let breakdown = board.score_breakdown(&s.word, s.x, s.y, s.horizontal)?;
+            assert_eq!(breakdown.total, s.score);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_play_tiles_errors() {
+        let mut board = board_nl().with_state_from_strings(TEST_STATE).unwrap();
+        let tiles: Word = board.encode("ab").unwrap();
+        let play = |board: &mut Board, placement: Vec<(usize, usize, Tile)>| {
+            board.play_tiles(&Placement::from(placement), false)
+        };
+        assert!(matches!(
+            play(&mut board, vec![]),
+            Err(Error::NoTilesPlaced)
+        ));
+        assert!(matches!(
+            play(&mut board, vec![(15, 0, tiles[0])]),
+            Err(Error::TilePlacementError { x: 15, y: 0, .. })
+        ));
+        assert!(matches!(
+            play(&mut board, vec![(4, 0, tiles[0])]),
+            Err(Error::TileReplaceError { x: 4, y: 0 })
+        ));
+        assert!(matches!(
+            play(&mut board, vec![(0, 0, tiles[0]), (1, 1, tiles[1])]),
+            Err(Error::NotContiguous { x: 1, y: 1 })
+        ));
+        assert!(matches!(
+            play(&mut board, vec![(0, 0, tiles[0]), (2, 0, tiles[1])]),
+            Err(Error::NotContiguous { x: 1, y: 0 })
+        ));
+    }
+
     #[test]
     #[ignore]
     fn test_sample_scores() -> Result<()> {
         let board = board_nl()
             .with_wordlist_from_file("../wordlists/wordlist-nl.txt")?
-            .with_state_from_strings(&TEST_STATE)?;
+            .with_state_from_strings(TEST_STATE)?;
         // let letters = "ehkmopp";
         // board.play_word("hoppe", 7,7, true, true)?;
         let racks: Vec<Letters> = [
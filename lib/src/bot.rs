@@ -0,0 +1,276 @@
+use crate::grid::Cell::{LetterBonus, WordBonus};
+use crate::strategy::{GameView, Strategy};
+use crate::{Board, Error, List, Move, Score};
+use rand::distributions::{Distribution, WeightedIndex};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use std::collections::HashMap;
+use std::fs::read_to_string;
+
+/// Words ranked by how common they are, to limit the vocabulary of a [`BotProfile`].
+#[derive(Debug, Clone, Default)]
+pub struct FrequencyList {
+    ranks: HashMap<String, usize>,
+}
+
+impl FrequencyList {
+    /// Create a frequency list from `words`, most common word first.
+    pub fn from_words(words: &[&str]) -> FrequencyList {
+        let mut ranks = HashMap::new();
+        for (rank, word) in words.iter().enumerate() {
+            ranks.entry(word.to_lowercase()).or_insert(rank);
+        }
+        FrequencyList { ranks }
+    }
+
+    /// Read a frequency list from the text file at `path`.
+    ///
+    /// Each line starts with a word, most common word first. Anything after the word, such as a count, is ignored.
+    /// Empty lines are ignored. The words are not checked against a word list,
+    /// and are matched case-insensitively with the words the bot can play.
+    /// ## Errors
+    /// If the file can not be read.
+    pub fn from_file(path: &str) -> Result<FrequencyList, Error> {
+        let text = read_to_string(path).map_err(|source| Error::ReadError {
+            path: String::from(path),
+            source,
+        })?;
+        let words: Vec<&str> = text
+            .lines()
+            .filter_map(|line| line.split_whitespace().next())
+            .collect();
+        Ok(FrequencyList::from_words(&words))
+    }
+
+    /// Return the rank of `word`: 0 for the most common word, or `None` if the word is not in the list.
+    pub fn rank(&self, word: &str) -> Option<usize> {
+        self.ranks.get(&word.to_lowercase()).copied()
+    }
+
+    /// Return the number of words in the list
+    pub fn len(&self) -> usize {
+        self.ranks.len()
+    }
+
+    /// Check if the list is empty
+    pub fn is_empty(&self) -> bool {
+        self.ranks.is_empty()
+    }
+}
+
+/// A bot that plays like a human of a given level.
+///
+/// The bot considers the moves from [`Board::calc_all_word_scores`] and deliberately restricts itself:
+/// - it only plays the `vocabulary` most common words of the `frequencies` list,
+/// - it only plays words up to `max_word_length` letters,
+/// - it overlooks moves on bonus squares with probability `miss_bonus`,
+/// - it chooses among the `top_k` best remaining moves, with a probability proportional to
+///   `exp(score / temperature)`. With temperature 0 it plays the best move.
+///
+/// Only the main word is checked against the vocabulary, not the crossing words.
+/// The random generator is derived from `seed` and the turn, so a game with bots is reproducible.
+///
+/// ## Examples
+/// ```
+/// # use wordfeud_solver::{Board, BotProfile, Error, Game, Strategy};
+/// let board = Board::default().with_wordlist_from_words(&["rust", "rest"])?;
+/// let mut game = Game::new(board, 2, 42)?;
+/// let bot = BotProfile::level(1, 7);
+/// let action = bot.choose(&game.view())?;
+/// game.play(action)?;
+/// # Ok::<(), Error>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct BotProfile {
+    /// seed for the random generator
+    pub seed: u64,
+    /// words ranked by how common they are
+    pub frequencies: Option<FrequencyList>,
+    /// number of most common words that the bot knows, if `frequencies` is set
+    pub vocabulary: Option<usize>,
+    /// maximum number of letters of a word
+    pub max_word_length: Option<usize>,
+    /// number of best moves to choose from
+    pub top_k: usize,
+    /// randomness of the choice among the best moves, in points
+    pub temperature: f32,
+    /// probability to overlook the moves on bonus squares in a turn
+    pub miss_bonus: f64,
+}
+
+/// Settings of a level: vocabulary, maximum word length, top k, temperature and probability to miss bonus squares
+type Level = (Option<usize>, Option<usize>, usize, f32, f64);
+
+const LEVELS: [Level; 5] = [
+    (Some(1_000), Some(5), 10, 10.0, 0.5),
+    (Some(5_000), Some(6), 8, 6.0, 0.3),
+    (Some(20_000), Some(8), 5, 4.0, 0.15),
+    (Some(50_000), Some(10), 3, 2.0, 0.05),
+    (None, None, 1, 0.0, 0.0),
+];
+
+impl BotProfile {
+    /// Return the profile for `level` 1 (beginner) to 5 (expert), with `seed`.
+    /// Levels outside that range are clamped. Set `frequencies` to limit the vocabulary.
+    pub fn level(level: usize, seed: u64) -> BotProfile {
+        let (vocabulary, max_word_length, top_k, temperature, miss_bonus) =
+            LEVELS[level.clamp(1, LEVELS.len()) - 1];
+        BotProfile {
+            seed,
+            frequencies: None,
+            vocabulary,
+            max_word_length,
+            top_k,
+            temperature,
+            miss_bonus,
+        }
+    }
+
+    /// Return the profile with the word `frequencies`.
+    pub fn with_frequencies(mut self, frequencies: FrequencyList) -> BotProfile {
+        self.frequencies = Some(frequencies);
+        self
+    }
+
+    /// Check if the bot knows `word`.
+    fn knows(&self, word: &str) -> bool {
+        match (&self.frequencies, self.vocabulary) {
+            (Some(frequencies), Some(vocabulary)) => {
+                frequencies.rank(word).is_some_and(|rank| rank < vocabulary)
+            }
+            _ => true,
+        }
+    }
+
+    /// Return the moves the bot considers, best first.
+    fn candidates<R: Rng>(&self, board: &Board, words: Vec<Score>, rng: &mut R) -> Vec<Score> {
+        let mut words: Vec<Score> = words
+            .into_iter()
+            .filter(|s| match self.max_word_length {
+                Some(max) => s.word.len() <= max,
+                None => true,
+            })
+            .filter(|s| self.knows(&board.decode(s.word)))
+            .collect();
+        if rng.gen_bool(self.miss_bonus.clamp(0.0, 1.0)) {
+            let grid = board.grid();
+            let plain: Vec<Score> = words
+                .iter()
+                .filter(|s| {
+                    board
+                        .word_placement(s.word, s.x, s.y, s.horizontal)
+                        .iter()
+                        .all(|&(x, y, _)| !matches!(grid[y][x], LetterBonus(_) | WordBonus(_)))
+                })
+                .cloned()
+                .collect();
+            if !plain.is_empty() {
+                words = plain;
+            }
+        }
+        words.sort_by(|a, b| b.cmp(a));
+        words.truncate(self.top_k.max(1));
+        words
+    }
+}
+
+impl Strategy for BotProfile {
+    fn choose(&self, view: &GameView) -> Result<Move, Error> {
+        let mut rng = StdRng::seed_from_u64(self.seed.wrapping_add(view.turn as u64));
+        let words = view.board.calc_all_word_scores(view.rack)?;
+        let candidates = self.candidates(view.board, words, &mut rng);
+        let best = match candidates.first() {
+            Some(best) => best.score as f32,
+            None => return Ok(Move::Pass),
+        };
+        let mut chosen = 0;
+        if self.temperature > 0.0 {
+            let weights = candidates
+                .iter()
+                .map(|s| ((s.score as f32 - best) / self.temperature).exp());
+            if let Ok(index) = WeightedIndex::new(weights) {
+                chosen = index.sample(&mut rng);
+            }
+        }
+        let s = candidates[chosen];
+        Ok(Move::Tiles(view.board.word_placement(
+            s.word,
+            s.x,
+            s.y,
+            s.horizontal,
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::game;
+    use crate::{Game, Greedy};
+
+    type Result<T> = std::result::Result<T, Error>;
+
+    fn word(game: &Game, action: &Move) -> String {
+        match action {
+            Move::Tiles(placement) => game
+                .board()
+                .clone()
+                .play_tiles(placement, false)
+                .map(|play| game.board().decode(play.word))
+                .unwrap_or_default(),
+            _ => String::new(),
+        }
+    }
+
+    #[test]
+    fn test_frequency_list() -> Result<()> {
+        let path =
+            std::env::temp_dir().join(format!("wordfeud_frequencies_{}.txt", std::process::id()));
+        std::fs::write(&path, "de 1000\nen 800\n\nHet 500\n").unwrap();
+        let frequencies = FrequencyList::from_file(path.to_str().unwrap())?;
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(frequencies.len(), 3);
+        assert_eq!(frequencies.rank("de"), Some(0));
+        assert_eq!(frequencies.rank("het"), Some(2));
+        assert_eq!(frequencies.rank("rust"), None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_restrictions() -> Result<()> {
+        let frequencies = FrequencyList::from_words(&["en", "ne", "net", "ten", "er", "re"]);
+        for seed in 0..10 {
+            let game = game(seed)?;
+            let view = game.view();
+            let bot = BotProfile {
+                vocabulary: Some(3),
+                max_word_length: Some(2),
+                ..BotProfile::level(1, seed)
+            }
+            .with_frequencies(frequencies.clone());
+            let played = word(&game, &bot.choose(&view)?);
+            assert!(played.is_empty() || ["en", "ne"].contains(&played.as_str()));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_levels() -> Result<()> {
+        let game = game(3)?;
+        let view = game.view();
+        // the expert plays a move with the best score
+        let points = |action: Move| -> Result<u32> {
+            match action {
+                Move::Tiles(placement) => {
+                    Ok(game.board().clone().play_tiles(&placement, false)?.score)
+                }
+                _ => Ok(0),
+            }
+        };
+        let expert = BotProfile::level(5, 0).choose(&view)?;
+        assert_eq!(points(expert)?, points(Greedy.choose(&view)?)?);
+        // a bot is reproducible from its seed
+        let beginner = BotProfile::level(1, 42);
+        assert_eq!(beginner.choose(&view)?, beginner.choose(&view)?);
+        Ok(())
+    }
+}
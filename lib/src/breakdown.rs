@@ -0,0 +1,56 @@
+use crate::grid::Cell as GridCell;
+use crate::{Tile, Word};
+
+/// Points for a single tile in a word.
+#[derive(Debug, Clone, Copy)]
+pub struct TilePoints {
+    /// tile x: 0..N
+    pub x: usize,
+    /// tile y: 0..N
+    pub y: usize,
+    /// the tile
+    pub tile: Tile,
+    /// value of the letter, 0 for a blank
+    pub letter_points: u32,
+    /// value of the letter with letter bonus applied
+    pub points: u32,
+    /// bonus square activated by this tile.
+    /// None if the tile was already on the board, or is placed on a square without bonus.
+    pub bonus: Option<GridCell>,
+}
+
+/// Points for a single word in a move.
+#[derive(Debug, Clone)]
+pub struct WordPoints {
+    /// word start x: 0..N
+    pub x: usize,
+    /// word start y: 0..N
+    pub y: usize,
+    /// horizontal if true, else vertical
+    pub horizontal: bool,
+    /// word as Tiles
+    pub word: Word,
+    /// points for each tile in the word
+    pub tiles: Vec<TilePoints>,
+    /// product of the activated word bonuses
+    pub word_multiplier: u32,
+    /// points for the word: the sum of the tile points times `word_multiplier`
+    pub points: u32,
+}
+
+/// Detailed score of a move: the points for the main word and each crossing word,
+/// and the bingo bonus.
+/// See [`Board::score_breakdown`](crate::Board::score_breakdown).
+#[derive(Debug, Clone)]
+pub struct ScoreBreakdown {
+    /// the word formed in the direction of the move
+    pub main_word: WordPoints,
+    /// words formed in the crossing direction
+    pub cross_words: Vec<WordPoints>,
+    /// true if all [`RACK_SIZE`](crate::RACK_SIZE) tiles of a full rack were played
+    pub bingo: bool,
+    /// the bingo bonus: [`BINGO_BONUS`](crate::BINGO_BONUS) points if `bingo`, else 0
+    pub bingo_bonus: u32,
+    /// the total score of the move
+    pub total: u32,
+}
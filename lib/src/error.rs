@@ -10,6 +10,19 @@ pub enum Error {
         source: std::io::Error,
     },
 
+    /// Error writing wordfile
+    #[cfg(feature = "mmap")]
+    #[error("Wordfile \"{path}\" could not be written")]
+    WriteError {
+        path: String,
+        source: std::io::Error,
+    },
+
+    /// The wordfile is not a valid memory-mapped wordlist file
+    #[cfg(feature = "mmap")]
+    #[error("Wordfile \"{path}\" is not a valid wordlist file: {reason}")]
+    InvalidWordfile { path: String, reason: String },
+
     /// Error deserializing bincoded wordfile
     #[cfg(feature = "bincode")]
     #[error("Wordfile {0} could not be deserialized")]
@@ -55,4 +68,69 @@ pub enum Error {
     /// Attempt to replace a tile already on the board
     #[error("Attempt to replace tile at x:{x}, y:{y}")]
     TileReplaceError { x: usize, y: usize },
+
+    /// A move that places no tiles on the board
+    #[error("No tiles placed")]
+    NoTilesPlaced,
+
+    /// The tiles of a move do not form one contiguous word
+    #[error("Tiles are not contiguous at x:{x}, y:{y}")]
+    NotContiguous { x: usize, y: usize },
+
+    /// The main word of a move is not in the wordlist
+    #[error("Word \"{0}\" is not in the wordlist")]
+    WordNotInWordlist(String),
+
+    /// A word formed in the crossing direction is not in the wordlist
+    #[error("Crossing word \"{word}\" at x:{x}, y:{y} is not in the wordlist")]
+    InvalidCrossWord { word: String, x: usize, y: usize },
+
+    /// The first move does not use the start square
+    #[error("The first word must use the start square")]
+    StartSquareNotUsed,
+
+    /// The move does not connect to the tiles on the board
+    #[error("Word is not connected to the tiles on the board")]
+    NotConnected,
+
+    /// The move uses letters that are not in the rack
+    #[error("Letters \"{0}\" are not in the rack")]
+    LettersNotInRack(String),
+
+    /// A move that violates one or more rules of the game
+    #[error("Invalid move: {}", join_errors(.0))]
+    InvalidMove(Vec<Error>),
+
+    /// A game needs 2 to 4 players
+    #[error("Invalid number of players {0} (expect 2..4)")]
+    InvalidPlayerCount(usize),
+
+    /// Attempt to play a move when the game is over
+    #[error("The game is over")]
+    GameOver,
+
+    /// Error parsing a line of a leave table file
+    #[error("Invalid leave table line: \"{0}\"")]
+    LeaveTableParseError(String),
+
+    /// Error creating a thread pool
+    #[cfg(feature = "rayon")]
+    #[error("Thread pool could not be created: {0}")]
+    ThreadPoolError(String),
+
+    /// Swapping letters needs at least 7 tiles in the bag
+    #[error("Swap not allowed with {0} tiles in the bag")]
+    SwapNotAllowed(usize),
+
+    /// A swap must exchange at least one letter
+    #[error("Swap without letters")]
+    EmptySwap,
+}
+
+fn join_errors(errors: &[Error]) -> String {
+    errors
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<String>>()
+        .join("; ")
 }
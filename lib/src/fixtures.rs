@@ -0,0 +1,19 @@
+//! Fixtures shared by the unit tests.
+use crate::{Board, Error, Game, Language};
+
+/// A small Dutch word list, with enough words to play a game
+pub(crate) const WORDS: &[&str] = &[
+    "af", "ah", "al", "aar", "aas", "be", "bi", "bo", "bar", "bes", "bel", "de", "do", "en", "er",
+    "et", "in", "is", "na", "ne", "no", "nu", "of", "op", "re", "so", "te", "to", "tv", "ze", "zo",
+    "ren", "ton", "net", "ster", "rest", "rust", "toen", "tien", "stern",
+];
+
+/// An empty Dutch board with [`WORDS`]
+pub(crate) fn board<'a>() -> Result<Board<'a>, Error> {
+    Board::new(Language::NL).with_wordlist_from_words(WORDS)
+}
+
+/// A new game of two players on [`board`]
+pub(crate) fn game<'a>(seed: u64) -> Result<Game<'a>, Error> {
+    Game::new(board()?, 2, seed)
+}
@@ -0,0 +1,360 @@
+use crate::ai::{remaining_tiles, tiles_score};
+use crate::placement::Placement;
+use crate::tilebag::TileBag;
+use crate::{Board, Code, Error, Item, Letter, Letters, List};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use std::convert::TryFrom;
+
+/// Number of letters on a full rack
+pub const RACK_SIZE: usize = 7;
+
+/// Bonus in points for a move that uses all [`RACK_SIZE`] letters of a full rack
+pub const BINGO_BONUS: u32 = 40;
+
+/// The game ends when each player passed or swapped this many times in a row.
+pub(crate) const MAX_SCORELESS_TURNS: usize = 2;
+
+/// A move by a player in a [`Game`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Move {
+    /// Place tiles on the board
+    Tiles(Placement),
+    /// Exchange letters from the rack with tiles from the bag
+    Swap(Letters),
+    /// Pass the turn
+    Pass,
+}
+
+/// A player in a [`Game`].
+#[derive(Debug, Clone, Default)]
+pub struct Player {
+    /// letters on the rack
+    pub rack: Letters,
+    /// total score, including the rack adjustment when the game is over
+    pub score: i32,
+}
+
+/// A turn that was played in a [`Game`].
+#[derive(Debug, Clone)]
+pub struct Turn {
+    /// index of the player
+    pub player: usize,
+    /// the move that was played
+    pub action: Move,
+    /// points scored with the move
+    pub score: u32,
+    /// the rack of the player before the move
+    pub rack: Letters,
+}
+
+/// A game of wordfeud between 2 to 4 players.
+///
+/// The game keeps track of the board, the racks and scores of the players, whose turn it is,
+/// and the tiles in the bag. Tiles are drawn from the bag with a seeded random generator,
+/// so a game is reproducible from its seed and moves.
+///
+/// The game is over when a player has used all letters and the bag is empty,
+/// or when all players passed or swapped twice in a row.
+/// The value of the letters left on the rack is then subtracted from the score of each player,
+/// and a player that used all letters gets the value of the letters of the other players.
+///
+/// ## Examples
+/// ```
+/// # use wordfeud_solver::{Board, Error, Game, List, Move};
+/// let board = Board::default().with_wordlist_from_words(&["rust", "rest"])?;
+/// let mut game = Game::new(board, 2, 42)?;
+/// assert_eq!(game.rack().len(), 7);
+/// game.play(Move::Pass)?;
+/// assert_eq!(game.current_player(), 1);
+/// # Ok::<(), Error>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct Game<'a> {
+    board: Board<'a>,
+    players: Vec<Player>,
+    bag: Vec<Code>,
+    rng: StdRng,
+    current: usize,
+    scoreless_turns: usize,
+    turns: Vec<Turn>,
+    over: bool,
+}
+
+impl<'a> Game<'a> {
+    /// Start a new game on `board` with `nplayers` players.
+    /// The bag holds the tiles of the tileset of `board` that are not on the board,
+    /// and each player draws a rack from the bag, using a random generator seeded with `seed`.
+    /// ## Errors
+    /// - [`InvalidPlayerCount`](Error::InvalidPlayerCount) if `nplayers` is not in 2..=4.
+    /// - [`InvalidLetterCode`](Error::InvalidLetterCode) if a tile drawn from the bag is not a valid letter.
+    pub fn new(board: Board<'a>, nplayers: usize, seed: u64) -> Result<Game<'a>, Error> {
+        if !(2..=4).contains(&nplayers) {
+            return Err(Error::InvalidPlayerCount(nplayers));
+        }
+        let full_bag = TileBag::from(board.tileset());
+        let bag = remaining_tiles(&full_bag, &board, Letters::new()).sorted();
+        let mut game = Game {
+            board,
+            players: vec![Player::default(); nplayers],
+            bag,
+            rng: StdRng::seed_from_u64(seed),
+            current: 0,
+            scoreless_turns: 0,
+            turns: Vec::new(),
+            over: false,
+        };
+        for player in 0..nplayers {
+            game.refill(player)?;
+        }
+        Ok(game)
+    }
+
+    /// Return the board
+    pub fn board(&self) -> &Board<'a> {
+        &self.board
+    }
+
+    /// Return the players
+    pub fn players(&self) -> &[Player] {
+        &self.players
+    }
+
+    /// Return the index of the player whose turn it is
+    pub fn current_player(&self) -> usize {
+        self.current
+    }
+
+    /// Return the rack of the player whose turn it is
+    pub fn rack(&self) -> Letters {
+        self.players[self.current].rack
+    }
+
+    /// Return the number of tiles in the bag
+    pub fn bag_len(&self) -> usize {
+        self.bag.len()
+    }
+
+    /// Return the turns played so far, oldest turn first
+    pub fn turns(&self) -> &[Turn] {
+        &self.turns
+    }
+
+    /// Check if the game is over
+    pub fn is_over(&self) -> bool {
+        self.over
+    }
+
+    /// Play `action` for the current player, and pass the turn to the next player.
+    /// Returns the points scored with the move.
+    /// ## Errors
+    /// - [`GameOver`](Error::GameOver) if the game is over.
+    /// - If the placed tiles do not form a legal move, see [`Board::play_tiles`] and [`Board::validate_move`].
+    /// - [`EmptySwap`](Error::EmptySwap) if a swap has no letters, use [`Move::Pass`] to pass.
+    /// - [`SwapNotAllowed`](Error::SwapNotAllowed) if there are less than 7 tiles in the bag.
+    /// - [`LettersNotInRack`](Error::LettersNotInRack) if the swapped letters are not in the rack.
+    /// - [`InvalidLetterCode`](Error::InvalidLetterCode) if a tile drawn from the bag is not a valid letter.
+    pub fn play(&mut self, action: Move) -> Result<u32, Error> {
+        if self.over {
+            return Err(Error::GameOver);
+        }
+        let player = self.current;
+        let rack = self.players[player].rack;
+        let score = match &action {
+            Move::Tiles(placement) => {
+                let play = self.board.play_tiles(placement, false)?;
+                let violations =
+                    self.board
+                        .move_violations(play.word, play.x, play.y, play.horizontal, rack)?;
+                if !violations.is_empty() {
+                    return Err(Error::InvalidMove(violations));
+                }
+                self.board.play(placement)?;
+                self.players[player].rack = rack
+                    .remove_letters(play.used)
+                    .map_err(|missing| Error::LettersNotInRack(self.board.decode(missing)))?;
+                self.refill(player)?;
+                self.scoreless_turns = 0;
+                play.score
+            }
+            Move::Swap(letters) => {
+                if letters.is_empty() {
+                    return Err(Error::EmptySwap);
+                }
+                if self.bag.len() < RACK_SIZE {
+                    return Err(Error::SwapNotAllowed(self.bag.len()));
+                }
+                self.players[player].rack = rack
+                    .remove_letters(*letters)
+                    .map_err(|missing| Error::LettersNotInRack(self.board.decode(missing)))?;
+                self.refill(player)?;
+                self.bag
+                    .extend(letters.into_iter().map(|letter| letter.code()));
+                self.scoreless_turns += 1;
+                0
+            }
+            Move::Pass => {
+                self.scoreless_turns += 1;
+                0
+            }
+        };
+        self.players[player].score += score as i32;
+        self.turns.push(Turn {
+            player,
+            action,
+            score,
+            rack,
+        });
+
+        if self.players[player].rack.is_empty() && self.bag.is_empty() {
+            self.finish(Some(player));
+        } else if self.scoreless_turns >= MAX_SCORELESS_TURNS * self.players.len() {
+            self.finish(None);
+        } else {
+            self.current = (player + 1) % self.players.len();
+        }
+        Ok(score)
+    }
+
+    /// Draw tiles from the bag until the rack of `player` is full or the bag is empty.
+    /// ## Errors
+    /// If a tile in the bag is not a valid letter code. The tile is left in the bag.
+    fn refill(&mut self, player: usize) -> Result<(), Error> {
+        let rack = &mut self.players[player].rack;
+        while rack.len() < RACK_SIZE && !self.bag.is_empty() {
+            let i = self.rng.gen_range(0..self.bag.len());
+            rack.push(Letter::try_from(self.bag[i])?);
+            self.bag.swap_remove(i);
+        }
+        Ok(())
+    }
+
+    /// Subtract the value of the letters on each rack from the score of each player.
+    /// If player `out` used all letters, the value of the other racks is added to their score.
+    fn finish(&mut self, out: Option<usize>) {
+        let tileset = self.board.tileset();
+        let penalties: Vec<i32> = self
+            .players
+            .iter()
+            .map(|player| tiles_score(&player.rack, tileset))
+            .collect();
+        for (player, penalty) in self.players.iter_mut().zip(&penalties) {
+            player.score -= penalty;
+        }
+        if let Some(out) = out {
+            self.players[out].score += penalties.iter().sum::<i32>();
+        }
+        self.over = true;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::board;
+
+    type Result<T> = std::result::Result<T, Error>;
+
+    #[test]
+    fn test_new() -> Result<()> {
+        assert!(matches!(
+            Game::new(board()?, 1, 0),
+            Err(Error::InvalidPlayerCount(1))
+        ));
+        assert!(matches!(
+            Game::new(board()?, 5, 0),
+            Err(Error::InvalidPlayerCount(5))
+        ));
+        let game = Game::new(board()?, 3, 0)?;
+        let full_bag = TileBag::from(game.board().tileset());
+        assert_eq!(game.bag_len() + 3 * RACK_SIZE, full_bag.len());
+        assert!(game.players().iter().all(|p| p.rack.len() == RACK_SIZE));
+
+        // the same seed draws the same racks
+        let other = Game::new(board()?, 3, 0)?;
+        for (p, q) in game.players().iter().zip(other.players()) {
+            assert_eq!(p.rack, q.rack);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_moves() -> Result<()> {
+        let mut game = Game::new(board()?, 2, 1)?;
+        let rack = game.rack();
+        // swap the first two letters
+        let swapped: Letters = rack.iter().take(2).cloned().collect();
+        assert_eq!(game.play(Move::Swap(swapped))?, 0);
+        assert_eq!(game.current_player(), 1);
+        assert_eq!(game.players()[0].rack.len(), RACK_SIZE);
+
+        // a swap without letters is not a pass
+        assert!(matches!(
+            game.play(Move::Swap(Letters::new())),
+            Err(Error::EmptySwap)
+        ));
+
+        // letters that are not in the rack can not be swapped
+        let mut letters = game.rack();
+        letters.push(Letter::blank());
+        assert!(matches!(
+            game.play(Move::Swap(letters)),
+            Err(Error::LettersNotInRack(_))
+        ));
+
+        // a move that is not in the wordlist is rejected
+        let tiles: crate::Word = game.board().encode("xx")?;
+        let placement = game.board().word_placement(tiles, 7, 7, true);
+        assert!(matches!(
+            game.play(Move::Tiles(placement)),
+            Err(Error::InvalidMove(_))
+        ));
+        assert_eq!(game.current_player(), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_full_game() -> Result<()> {
+        let mut game = Game::new(board()?, 2, 7)?;
+        while !game.is_over() {
+            let mut scores = game.board().calc_all_word_scores(game.rack())?;
+            scores.sort_by_key(|s| std::cmp::Reverse(s.score));
+            let action = match scores.first() {
+                Some(s) => Move::Tiles(game.board().word_placement(s.word, s.x, s.y, s.horizontal)),
+                None => Move::Pass,
+            };
+            game.play(action)?;
+        }
+        assert!(matches!(game.play(Move::Pass), Err(Error::GameOver)));
+        assert!(game
+            .turns()
+            .iter()
+            .any(|turn| matches!(turn.action, Move::Tiles(_))));
+
+        // the scores are the points of the moves, adjusted with the letters left on the racks
+        let tileset = game.board().tileset();
+        let out = game
+            .players()
+            .iter()
+            .position(|p| p.rack.is_empty() && game.bag_len() == 0);
+        let penalties: Vec<i32> = game
+            .players()
+            .iter()
+            .map(|p| tiles_score(&p.rack, tileset))
+            .collect();
+        for (i, player) in game.players().iter().enumerate() {
+            let points: u32 = game
+                .turns()
+                .iter()
+                .filter(|turn| turn.player == i)
+                .map(|turn| turn.score)
+                .sum();
+            let bonus = if out == Some(i) {
+                penalties.iter().sum()
+            } else {
+                0
+            };
+            assert_eq!(player.score, points as i32 - penalties[i] + bonus);
+        }
+        Ok(())
+    }
+}
@@ -112,6 +112,7 @@ impl Grid {
     /// let grid = Grid::default();
     /// println!("{}", grid);
     /// ```
+    #[allow(clippy::should_implement_trait)]
     pub fn default() -> Grid {
         Grid::expand_quarter_board(&_DEFAULT_QUARTER_BOARD)
     }
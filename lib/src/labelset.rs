@@ -35,8 +35,9 @@ fn zero_highbits(n: u32, v: u32) -> u32 {
 }
 
 /// A bitset representing labels present in a `wordlist` node
-#[derive(Clone, Copy, Default)]
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[repr(transparent)]
 pub struct LabelSet(u32);
 
 impl LabelSet {
@@ -141,12 +142,25 @@ impl From<Vec<u8>> for LabelSet {
     }
 }
 
+#[allow(clippy::from_over_into)]
 impl Into<Vec<u8>> for LabelSet {
     fn into(self) -> Vec<u8> {
         self.iter().collect()
     }
 }
 
+impl From<u32> for LabelSet {
+    fn from(bits: u32) -> Self {
+        LabelSet(bits)
+    }
+}
+
+impl From<LabelSet> for u32 {
+    fn from(labels: LabelSet) -> Self {
+        labels.0
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
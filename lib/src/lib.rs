@@ -2,7 +2,7 @@
     html_logo_url = "https://github.com/jensanjo/wordfeud-solver/raw/master/images/logo.png",
     html_favicon_url = "https://github.com/jensanjo/wordfeud-solver/raw/master/images/logo.png"
 )]
-#![deny(clippy::wrong_pub_self_convention, clippy::used_underscore_binding,
+#![deny(clippy::wrong_self_convention, clippy::used_underscore_binding,
 //    clippy::pub_enum_variant_names,
 //    clippy::missing_docs_in_private_items,
 //    clippy::non_ascii_literal, clippy::unicode_not_nfc,
@@ -16,6 +16,7 @@
 //    clippy::cast_possible_wrap, clippy::cast_possible_truncation,
    clippy::mutex_integer, clippy::mut_mut, clippy::items_after_statements,
    clippy::print_stdout, clippy::mem_forget, clippy::maybe_infinite_iter)]
+#![cfg_attr(test, allow(clippy::print_stdout))]
 
 //! A wordfeud library for Rust.
 //! <br>
@@ -49,22 +50,47 @@
 //! ```
 mod ai;
 mod board;
+mod bot;
+mod breakdown;
 mod error;
+#[cfg(test)]
+mod fixtures;
+mod game;
 mod grid;
 mod labelset;
+mod placement;
+mod selfplay;
+mod strategy;
 mod tilebag;
 mod tiles;
 mod tilesets;
+mod tournament;
 mod wordlist;
 
-pub use crate::ai::{find_best_scores, remaining_tiles, Score as BestScore};
+pub use crate::ai::{
+    bingo_probability, evaluate_leaves, evaluate_risks, find_best_scores, find_best_scores_with,
+    has_bingo, leave_samples, rank_scores, remaining_tiles, simulate, solve_endgame, word_score,
+    EndgameLimits, EndgameMove, EndgameResult, Exposure, LeaveTable, MoveKind, OpponentModel,
+    Ranking, Score as BestScore, SearchConfig, SimScore, Simulation,
+};
 pub use crate::board::{Board, Score};
+pub use crate::bot::{BotProfile, FrequencyList};
+pub use crate::breakdown::{ScoreBreakdown, TilePoints, WordPoints};
 pub use crate::error::Error;
-pub use crate::grid::Grid;
+pub use crate::game::{Game, Move, Player, Turn, BINGO_BONUS, RACK_SIZE};
+pub use crate::grid::{Cell as GridCell, Grid};
+pub use crate::placement::{MoveRecord, Placement, Play};
+pub use crate::selfplay::{play_game, play_match, GameResult, MatchReport};
+pub use crate::strategy::{
+    Defensive, GameView, Greedy, GreedyLeave, RandomMove, Searched, Simulated, Strategy, Weakest,
+};
+pub use crate::tilebag::TileBag;
 pub use crate::tiles::{
     Cell, Code, Codec, Item, ItemList, Label, Letter, Letters, List, Row, Tile, Word,
 };
 pub use crate::tilesets::Language;
 pub use crate::tilesets::TileSet;
-pub use crate::tilebag::TileBag;
-pub use crate::wordlist::{RowData, Wordlist};
+pub use crate::tournament::{
+    Pairing, Rating, Standing, Tournament, TournamentGame, TournamentResult,
+};
+pub use crate::wordlist::{Gaddag, MoveGenerator, RowData, Wordlist};
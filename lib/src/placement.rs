@@ -0,0 +1,68 @@
+use crate::wordlist::RowData;
+use crate::{Letters, Tile, Word};
+use std::ops::Deref;
+
+/// Tiles placed on the board in a single move, as a list of (`x`, `y`, `tile`) tuples.
+///
+/// This is what a client sends when a player puts tiles on the board.
+/// See [`Board::play_tiles`](crate::Board::play_tiles).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Placement(Vec<(usize, usize, Tile)>);
+
+impl Deref for Placement {
+    type Target = [(usize, usize, Tile)];
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl Placement {
+    /// Return a new empty placement.
+    pub fn new() -> Placement {
+        Placement(Vec::new())
+    }
+
+    /// Place `tile` at `x`, `y`.
+    pub fn push(&mut self, x: usize, y: usize, tile: Tile) {
+        self.0.push((x, y, tile));
+    }
+}
+
+impl From<Vec<(usize, usize, Tile)>> for Placement {
+    fn from(tiles: Vec<(usize, usize, Tile)>) -> Self {
+        Placement(tiles)
+    }
+}
+
+/// The move that results from a [`Placement`], as derived by [`Board::play_tiles`](crate::Board::play_tiles).
+#[derive(Debug, Clone)]
+pub struct Play {
+    /// main word start x: 0..N
+    pub x: usize,
+    /// main word start y: 0..N
+    pub y: usize,
+    /// horizontal if true, else vertical
+    pub horizontal: bool,
+    /// the main word, including the tiles that were already on the board
+    pub word: Word,
+    /// words formed in the crossing direction, as (`x`, `y`, `word`) tuples
+    pub cross_words: Vec<(usize, usize, Word)>,
+    /// score for the move, including the crossing words
+    pub score: u32,
+    /// letters used from the rack
+    pub used: Letters,
+}
+
+/// A move in the move history of a [`Board`](crate::Board).
+/// See [`Board::play`](crate::Board::play) and [`Board::undo`](crate::Board::undo).
+#[derive(Debug, Clone)]
+pub struct MoveRecord {
+    /// the tiles placed on the board
+    pub placement: Placement,
+    /// score for the move
+    pub score: u32,
+    /// rowdata of the rows affected by the move, as it was before the move:
+    /// a list of (`horizontal`, `index`, `rowdata`) tuples
+    pub(crate) rowdata: Vec<(bool, usize, RowData)>,
+}
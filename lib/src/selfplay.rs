@@ -0,0 +1,266 @@
+use crate::ai::Z_95;
+use crate::game::RACK_SIZE;
+use crate::strategy::Strategy;
+use crate::{Board, Error, Game, Move};
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+use std::fmt;
+
+/// Result of one game between two strategies, see [`play_game`].
+/// The arrays are indexed by strategy, not by the order of play.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct GameResult {
+    /// seed of the game
+    pub seed: u64,
+    /// index of the strategy that played first
+    pub first: usize,
+    /// final score of each strategy
+    pub scores: [i32; 2],
+    /// number of moves of each strategy, including swaps and passes
+    pub moves: [usize; 2],
+    /// points scored with the moves of each strategy, without the rack adjustment at the end
+    pub points: [u32; 2],
+    /// number of moves of each strategy that used all letters of a full rack
+    pub bingos: [usize; 2],
+}
+
+impl GameResult {
+    /// Return the final score of strategy 0 minus the final score of strategy 1
+    pub fn spread(&self) -> i32 {
+        self.scores[0] - self.scores[1]
+    }
+}
+
+/// Play a game on `board` between two `strategies`, with the bag drawn with `seed`.
+/// Strategy `first` plays first.
+/// ## Errors
+/// If a strategy fails, or chooses a move that can not be played.
+pub fn play_game<A, B>(
+    board: &Board,
+    strategies: (&A, &B),
+    first: usize,
+    seed: u64,
+) -> Result<GameResult, Error>
+where
+    A: Strategy + ?Sized,
+    B: Strategy + ?Sized,
+{
+    let mut game = Game::new(board.clone(), 2, seed)?;
+    // the strategy of player i is (i + first) % 2
+    let strategy = |player: usize| (player + first) % 2;
+    while !game.is_over() {
+        let view = game.view();
+        let action = match strategy(game.current_player()) {
+            0 => strategies.0.choose(&view)?,
+            _ => strategies.1.choose(&view)?,
+        };
+        game.play(action)?;
+    }
+    let mut result = GameResult {
+        seed,
+        first,
+        scores: [0; 2],
+        moves: [0; 2],
+        points: [0; 2],
+        bingos: [0; 2],
+    };
+    for (player, p) in game.players().iter().enumerate() {
+        result.scores[strategy(player)] = p.score;
+    }
+    for turn in game.turns() {
+        let i = strategy(turn.player);
+        result.moves[i] += 1;
+        result.points[i] += turn.score;
+        if matches!(&turn.action, Move::Tiles(placement) if placement.len() == RACK_SIZE) {
+            result.bingos[i] += 1;
+        }
+    }
+    Ok(result)
+}
+
+/// Summary of a match between two strategies, see [`play_match`].
+/// The statistics are for strategy 0, or indexed by strategy.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct MatchReport {
+    /// result of each game
+    pub results: Vec<GameResult>,
+    /// number of games won by each strategy
+    pub wins: [usize; 2],
+    /// number of draws
+    pub draws: usize,
+    /// fraction of the games won by strategy 0, counting a draw as half a win
+    pub win_rate: f64,
+    /// 95% confidence interval of `win_rate`
+    pub win_rate_interval: (f64, f64),
+    /// mean final score of strategy 0 minus the final score of strategy 1
+    pub mean_spread: f64,
+    /// 95% confidence interval of `mean_spread`
+    pub spread_interval: (f64, f64),
+    /// mean points per move of each strategy
+    pub score_per_move: [f64; 2],
+    /// mean number of bingos per game of each strategy
+    pub bingos_per_game: [f64; 2],
+}
+
+impl MatchReport {
+    /// Summarize `results`.
+    pub fn new(results: Vec<GameResult>) -> MatchReport {
+        let n = results.len().max(1) as f64;
+        let mut wins = [0; 2];
+        let mut draws = 0;
+        let mut moves = [0; 2];
+        let mut points = [0; 2];
+        let mut bingos = [0; 2];
+        for r in &results {
+            match r.spread() {
+                s if s > 0 => wins[0] += 1,
+                s if s < 0 => wins[1] += 1,
+                _ => draws += 1,
+            }
+            for i in 0..2 {
+                moves[i] += r.moves[i];
+                points[i] += r.points[i];
+                bingos[i] += r.bingos[i];
+            }
+        }
+        let win_rate = (wins[0] as f64 + 0.5 * draws as f64) / n;
+        let half_width = f64::from(Z_95) * (win_rate * (1.0 - win_rate) / n).sqrt();
+        let spreads: Vec<f64> = results.iter().map(|r| r.spread() as f64).collect();
+        let mean_spread = spreads.iter().sum::<f64>() / n;
+        let variance = spreads
+            .iter()
+            .map(|s| (s - mean_spread).powi(2))
+            .sum::<f64>()
+            / n;
+        let spread_half_width = f64::from(Z_95) * (variance / n).sqrt();
+        MatchReport {
+            wins,
+            draws,
+            win_rate,
+            win_rate_interval: (
+                (win_rate - half_width).max(0.0),
+                (win_rate + half_width).min(1.0),
+            ),
+            mean_spread,
+            spread_interval: (
+                mean_spread - spread_half_width,
+                mean_spread + spread_half_width,
+            ),
+            score_per_move: [
+                points[0] as f64 / moves[0].max(1) as f64,
+                points[1] as f64 / moves[1].max(1) as f64,
+            ],
+            bingos_per_game: [bingos[0] as f64 / n, bingos[1] as f64 / n],
+            results,
+        }
+    }
+}
+
+impl fmt::Display for MatchReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "games: {} wins: {} - {} draws: {}",
+            self.results.len(),
+            self.wins[0],
+            self.wins[1],
+            self.draws
+        )?;
+        writeln!(
+            f,
+            "win rate: {:.3} ({:.3} .. {:.3})",
+            self.win_rate, self.win_rate_interval.0, self.win_rate_interval.1
+        )?;
+        writeln!(
+            f,
+            "mean spread: {:.1} ({:.1} .. {:.1})",
+            self.mean_spread, self.spread_interval.0, self.spread_interval.1
+        )?;
+        writeln!(
+            f,
+            "score per move: {:.1} - {:.1}",
+            self.score_per_move[0], self.score_per_move[1]
+        )?;
+        write!(
+            f,
+            "bingos per game: {:.2} - {:.2}",
+            self.bingos_per_game[0], self.bingos_per_game[1]
+        )
+    }
+}
+
+/// Play `games` games on `board` between strategies `a` (0) and `b` (1), and summarize the results.
+///
+/// Game `i` is played with seed `seed + i`, and the strategies take turns to play first.
+/// The games are played in parallel with the `rayon` feature.
+/// ## Errors
+/// If a game fails, see [`play_game`].
+///
+/// ## Examples
+/// ```
+/// # use wordfeud_solver::{play_match, Board, Error, Greedy, Weakest};
+/// let board = Board::default().with_wordlist_from_words(&["rust", "rest", "at", "ta"])?;
+/// let report = play_match(&board, &Greedy, &Weakest, 4, 0)?;
+/// assert_eq!(report.results.len(), 4);
+/// # Ok::<(), Error>(())
+/// ```
+pub fn play_match<A, B>(
+    board: &Board,
+    a: &A,
+    b: &B,
+    games: usize,
+    seed: u64,
+) -> Result<MatchReport, Error>
+where
+    A: Strategy + Sync + ?Sized,
+    B: Strategy + Sync + ?Sized,
+{
+    let play = |i: usize| play_game(board, (a, b), i % 2, seed.wrapping_add(i as u64));
+    let results;
+    #[cfg(not(feature = "rayon"))]
+    {
+        results = (0..games).map(play).collect::<Result<Vec<_>, _>>()?;
+    }
+    #[cfg(feature = "rayon")]
+    {
+        results = (0..games)
+            .into_par_iter()
+            .map(play)
+            .collect::<Result<Vec<_>, _>>()?;
+    }
+    Ok(MatchReport::new(results))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::board;
+    use crate::{Greedy, Weakest};
+
+    type Result<T> = std::result::Result<T, Error>;
+
+    #[test]
+    fn test_play_match() -> Result<()> {
+        let board = board()?;
+        let report = play_match(&board, &Greedy, &Weakest, 6, 10)?;
+        assert_eq!(report.results.len(), 6);
+        assert_eq!(report.wins[0] + report.wins[1] + report.draws, 6);
+        assert!(report.score_per_move[0] > report.score_per_move[1]);
+        assert!(report.win_rate_interval.0 <= report.win_rate);
+        assert!(report.spread_interval.1 >= report.mean_spread);
+        for (i, r) in report.results.iter().enumerate() {
+            assert_eq!(r.first, i % 2);
+            assert_eq!(r.seed, 10 + i as u64);
+        }
+
+        // the results are reproducible
+        let again = play_match(&board, &Greedy, &Weakest, 6, 10)?;
+        let spreads = |report: &MatchReport| -> Vec<i32> {
+            report.results.iter().map(GameResult::spread).collect()
+        };
+        assert_eq!(spreads(&report), spreads(&again));
+        Ok(())
+    }
+}
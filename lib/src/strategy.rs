@@ -0,0 +1,298 @@
+use crate::ai::{
+    evaluate_leaves, evaluate_risks, find_best_scores_with, rank_scores, simulate, swap_candidates,
+    word_score, ExitFlag, LeaveTable, MoveKind, Ranking, Score, SearchConfig,
+};
+use crate::game::RACK_SIZE;
+use crate::{Board, Error, Game, Letters, List, Move};
+use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+use std::cmp::Reverse;
+
+/// What the player whose turn it is can see of a [`Game`].
+#[derive(Debug, Clone)]
+pub struct GameView<'g, 'a> {
+    /// the board
+    pub board: &'g Board<'a>,
+    /// the letters on the rack of the player
+    pub rack: Letters,
+    /// number of tiles in the bag
+    pub bag_len: usize,
+    /// index of the player
+    pub player: usize,
+    /// scores of all players
+    pub scores: Vec<i32>,
+    /// number of turns played in the game
+    pub turn: usize,
+    /// the last move in the game, if any
+    pub last_move: Option<&'g Move>,
+}
+
+impl<'a> Game<'a> {
+    /// Return what the current player can see of the game.
+    pub fn view(&self) -> GameView<'_, 'a> {
+        GameView {
+            board: self.board(),
+            rack: self.rack(),
+            bag_len: self.bag_len(),
+            player: self.current_player(),
+            scores: self.players().iter().map(|p| p.score).collect(),
+            turn: self.turns().len(),
+            last_move: self.turns().last().map(|turn| &turn.action),
+        }
+    }
+}
+
+/// A way to choose a move in a [`Game`].
+///
+/// All strategies generate moves with [`Board::calc_all_word_scores`], and differ in how they rank them.
+/// A strategy that chooses randomly is seeded, and derives its random generator from the seed and
+/// the turn, so a game played with strategies is reproducible.
+///
+/// ## Examples
+/// ```
+/// # use wordfeud_solver::{Board, Error, Game, Greedy, Strategy};
+/// let board = Board::default().with_wordlist_from_words(&["rust", "rest"])?;
+/// let mut game = Game::new(board, 2, 42)?;
+/// let action = Greedy.choose(&game.view())?;
+/// game.play(action)?;
+/// # Ok::<(), Error>(())
+/// ```
+pub trait Strategy {
+    /// Choose a move for the player in `view`.
+    /// ## Errors
+    /// If the moves can not be calculated.
+    fn choose(&self, view: &GameView) -> Result<Move, Error>;
+}
+
+/// Play the word with the most points.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Greedy;
+
+/// Play the move with the most points plus the value of the leave,
+/// or swap if that gives a better leave.
+#[derive(Debug, Clone)]
+pub struct GreedyLeave {
+    /// table to evaluate the leaves
+    pub table: LeaveTable,
+}
+
+/// Play the move with the best equity from [`find_best_scores_with`](crate::find_best_scores_with).
+#[derive(Debug, Clone, Default)]
+pub struct Searched {
+    /// parameters of the search
+    pub config: SearchConfig,
+}
+
+/// Play the best move from a [`simulate`](crate::simulate) simulation.
+#[derive(Debug, Clone, Default)]
+pub struct Simulated {
+    /// parameters of the simulation
+    pub config: SearchConfig,
+}
+
+/// Play the move with the most points plus the value of the leave, minus the risk of the board,
+/// see [`Exposure`](crate::Exposure).
+#[derive(Debug, Clone)]
+pub struct Defensive {
+    /// table to evaluate the leaves
+    pub table: LeaveTable,
+}
+
+/// Play a random word.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RandomMove {
+    /// seed for the random generator
+    pub seed: u64,
+}
+
+/// Play the word with the least points, for beginner bots.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Weakest;
+
+impl Strategy for Greedy {
+    fn choose(&self, view: &GameView) -> Result<Move, Error> {
+        let words = view.board.calc_all_word_scores(view.rack)?;
+        let best = words.iter().max();
+        Ok(word_move(view.board, best))
+    }
+}
+
+impl Strategy for GreedyLeave {
+    fn choose(&self, view: &GameView) -> Result<Move, Error> {
+        let mut scores = word_scores(view)?;
+        if view.bag_len >= RACK_SIZE {
+            scores.extend(swap_candidates(view.rack).into_iter().map(|swapped| Score {
+                played: view.board.decode(swapped),
+                kind: MoveKind::Swap,
+                ..empty_score()
+            }));
+        }
+        evaluate_leaves(view.board, view.rack, &mut scores, &self.table)?;
+        rank_scores(&mut scores, Ranking::Equity);
+        score_move(view.board, scores.first())
+    }
+}
+
+impl Strategy for Searched {
+    fn choose(&self, view: &GameView) -> Result<Move, Error> {
+        let mut board = view.board.clone();
+        let mut scores = find_best_scores_with(&mut board, view.rack, &self.config)?;
+        rank_scores(&mut scores, Ranking::Equity);
+        score_move(view.board, scores.first())
+    }
+}
+
+impl Strategy for Simulated {
+    fn choose(&self, view: &GameView) -> Result<Move, Error> {
+        let config = SearchConfig {
+            swaps: self.config.swaps && view.bag_len >= RACK_SIZE,
+            ..self.config.clone()
+        };
+        let ranking = simulate(view.board, view.rack, &config)?;
+        score_move(view.board, ranking.first().map(|s| &s.score))
+    }
+}
+
+impl Strategy for Defensive {
+    fn choose(&self, view: &GameView) -> Result<Move, Error> {
+        let mut scores = word_scores(view)?;
+        let mut board = view.board.clone();
+        evaluate_risks(&mut board, &mut scores)?;
+        evaluate_leaves(view.board, view.rack, &mut scores, &self.table)?;
+        rank_scores(&mut scores, Ranking::Defensive);
+        score_move(view.board, scores.first())
+    }
+}
+
+impl Strategy for RandomMove {
+    fn choose(&self, view: &GameView) -> Result<Move, Error> {
+        let mut rng = StdRng::seed_from_u64(self.seed.wrapping_add(view.turn as u64));
+        let words = view.board.calc_all_word_scores(view.rack)?;
+        Ok(word_move(view.board, words.choose(&mut rng)))
+    }
+}
+
+impl Strategy for Weakest {
+    fn choose(&self, view: &GameView) -> Result<Move, Error> {
+        let words = view.board.calc_all_word_scores(view.rack)?;
+        let weakest = words
+            .iter()
+            .min_by_key(|&s| (s.score, s.word.len(), Reverse(*s)));
+        Ok(word_move(view.board, weakest))
+    }
+}
+
+/// Return the word scores for the rack in `view`.
+fn word_scores(view: &GameView) -> Result<Vec<Score>, Error> {
+    Ok(view
+        .board
+        .calc_all_word_scores(view.rack)?
+        .iter()
+        .map(|s| word_score(view.board, s))
+        .collect())
+}
+
+fn empty_score() -> Score {
+    Score {
+        x: 0,
+        y: 0,
+        horizontal: true,
+        word: String::new(),
+        score: 0,
+        adj_score: 0,
+        opp_score: 0,
+        opp_std: 0.0,
+        played: String::new(),
+        exit_flag: ExitFlag::None,
+        opp_score_diff: 0,
+        kind: MoveKind::Pass,
+        leave_score: 0.0,
+        risk: 0.0,
+        bingo_bonus: 0.0,
+        swap_gain: 0.0,
+    }
+}
+
+/// Return the move to play word `s`, or pass if there is no word.
+fn word_move(board: &Board, s: Option<&crate::Score>) -> Move {
+    match s {
+        Some(s) => Move::Tiles(board.word_placement(s.word, s.x, s.y, s.horizontal)),
+        None => Move::Pass,
+    }
+}
+
+/// Return the move for `s`, or pass if there is no move.
+fn score_move(board: &Board, s: Option<&Score>) -> Result<Move, Error> {
+    s.map_or(Ok(Move::Pass), |s| s.to_move(board))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::board;
+    use crate::Language;
+
+    type Result<T> = std::result::Result<T, Error>;
+
+    fn points(board: &Board, action: &Move) -> Result<u32> {
+        Ok(match action {
+            Move::Tiles(placement) => board.clone().play_tiles(placement, false)?.score,
+            _ => 0,
+        })
+    }
+
+    #[test]
+    fn test_strategies() -> Result<()> {
+        let board = board()?;
+        let game = Game::new(board, 2, 11)?;
+        let view = game.view();
+        let greedy = points(view.board, &Greedy.choose(&view)?)?;
+        let weakest = points(view.board, &Weakest.choose(&view)?)?;
+        let random = points(view.board, &RandomMove { seed: 1 }.choose(&view)?)?;
+        assert!(weakest <= random && random <= greedy);
+        Ok(())
+    }
+
+    #[test]
+    fn test_play_games() -> Result<()> {
+        let table = LeaveTable::new(Language::NL);
+        let config = SearchConfig {
+            candidates: 4,
+            samples: 4,
+            ..SearchConfig::default()
+        };
+        let strategies: Vec<Box<dyn Strategy>> = vec![
+            Box::new(Greedy),
+            Box::new(GreedyLeave {
+                table: table.clone(),
+            }),
+            Box::new(Simulated {
+                config: config.clone(),
+            }),
+            Box::new(Defensive { table }),
+            Box::new(RandomMove { seed: 3 }),
+            Box::new(Weakest),
+            // evaluating every swap with refills is slow in a debug build
+            Box::new(Searched {
+                config: SearchConfig {
+                    swaps: false,
+                    ..config
+                },
+            }),
+        ];
+        for (i, strategy) in strategies.iter().enumerate() {
+            let board = board()?;
+            let mut game = Game::new(board, 2, i as u64)?;
+            let mut turns = 0;
+            while !game.is_over() && turns < 20 {
+                let action = strategy.choose(&game.view())?;
+                game.play(action)?;
+                turns += 1;
+            }
+            assert!(game
+                .turns()
+                .iter()
+                .any(|turn| matches!(turn.action, Move::Tiles(_))));
+        }
+        Ok(())
+    }
+}
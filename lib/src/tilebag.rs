@@ -26,6 +26,7 @@ impl Sub for TileBag {
 }
 
 impl TileBag {
+    #[allow(clippy::new_without_default)]
     pub fn new() -> Self {
         Self(HashMultiSet::new())
     }
@@ -42,6 +43,15 @@ impl TileBag {
         Self(bag)
     }
 
+    /// Return the tiles in the bag, sorted by code.
+    /// The iteration order of the bag is not reproducible, so random draws from the tiles
+    /// must use this order to be reproducible with a seed.
+    pub(crate) fn sorted(&self) -> Vec<Code> {
+        let mut tiles: Vec<Code> = self.iter().cloned().collect();
+        tiles.sort_unstable();
+        tiles
+    }
+
     fn from_tiles<T: AsRef<[Code]>>(tiles: T) -> TileBag {
         let mut bag = HashMultiSet::new();
         for &tile in tiles.as_ref() {
@@ -59,6 +59,7 @@ impl TryFrom<Code> for Cell {
     }
 }
 
+#[allow(clippy::from_over_into)]
 impl Into<Code> for Cell {
     fn into(self) -> Code {
         self.code()
@@ -87,6 +87,15 @@ impl Codec {
         }
     }
 
+    /// Return the letters of the codes 1 up to the blank tile, in order of their code.
+    #[cfg(feature = "mmap")]
+    pub(crate) fn alphabet(&self) -> String {
+        self.codeset.decoder[1..BLANK as usize]
+            .iter()
+            .flat_map(|chars| chars.iter().flatten())
+            .collect()
+    }
+
     /// A simple tokenizer for single char tiles (does not support spanish)
     fn tokenize(&self, word: &str) -> Tokens {
         word.chars().map(String::from).collect::<Vec<_>>()
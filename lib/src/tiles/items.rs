@@ -23,6 +23,25 @@ impl Letters {
         w.0.remove(pos);
         w
     }
+
+    /// Remove each of `letters` from self.
+    /// Returns the remaining letters, or `Err` with the letters that are not in self.
+    pub fn remove_letters(&self, letters: Letters) -> Result<Letters, Letters> {
+        let mut remaining = *self;
+        let mut missing = Letters::new();
+        for letter in letters {
+            if let Some(pos) = remaining.iter().position(|&l| l == letter) {
+                remaining = remaining.remove(pos);
+            } else {
+                missing.push(letter);
+            }
+        }
+        if missing.is_empty() {
+            Ok(remaining)
+        } else {
+            Err(missing)
+        }
+    }
 }
 
 /// A trait for things that can be converted to Letters by a Codec
@@ -103,7 +122,7 @@ impl Row {
     /// ## Panics
     /// if len > DIM
     pub fn empty_row(len: usize) -> Row {
-        let inner: Items<Cell> = (0..len).into_iter().map(|_| Cell::EMPTY).collect();
+        let inner: Items<Cell> = (0..len).map(|_| Cell::EMPTY).collect();
         ItemList::<Cell>(inner)
     }
 }
@@ -64,6 +64,7 @@ impl TryFrom<Code> for Letter {
     }
 }
 
+#[allow(clippy::from_over_into)]
 impl Into<Code> for Letter {
     fn into(self) -> Code {
         self.0.get()
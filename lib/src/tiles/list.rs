@@ -27,7 +27,7 @@ pub trait List:
 
     fn push(&mut self, item: Self::Item);
 
-    fn iter(&self) -> Iter<Self::Item>;
+    fn iter(&self) -> Iter<'_, Self::Item>;
 }
 
 pub(super) type Items<T> = ArrayVec<[T; DIM]>;
@@ -81,7 +81,7 @@ impl<T: Item> List for ItemList<T> {
         self.0.push(item);
     }
 
-    fn iter(&self) -> Iter<Self::Item> {
+    fn iter(&self) -> Iter<'_, Self::Item> {
         self.0.iter()
     }
 }
@@ -75,6 +75,7 @@ impl TryFrom<Code> for Tile {
     }
 }
 
+#[allow(clippy::from_over_into)]
 impl Into<Code> for Tile {
     fn into(self) -> Code {
         self.0.get()
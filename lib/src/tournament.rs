@@ -0,0 +1,492 @@
+use crate::selfplay::{play_match, GameResult};
+use crate::strategy::Strategy;
+use crate::{Board, Error};
+use std::f64::consts::PI;
+use std::io::{self, Write};
+
+/// Initial rating of an entrant
+const INITIAL_RATING: f64 = 1500.0;
+/// Maximum change of the Elo rating in one game
+const ELO_K: f64 = 32.0;
+/// Initial Glicko-2 rating deviation and volatility
+const INITIAL_DEVIATION: f64 = 350.0;
+const INITIAL_VOLATILITY: f64 = 0.06;
+/// Glicko-2 system constant, constrains the change in volatility
+const TAU: f64 = 0.5;
+/// Conversion between the Glicko and Glicko-2 scales
+const GLICKO2_SCALE: f64 = 173.7178;
+
+/// How the entrants of a [`Tournament`] are paired.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Pairing {
+    /// every entrant plays every other entrant, in one round
+    RoundRobin,
+    /// in each of `rounds` rounds, entrants with similar results play each other
+    Swiss {
+        /// number of rounds
+        rounds: usize,
+    },
+}
+
+/// A Glicko-2 rating, on the Glicko scale.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Rating {
+    /// the rating
+    pub rating: f64,
+    /// the rating deviation: the uncertainty of the rating
+    pub deviation: f64,
+    /// the volatility: the expected fluctuation of the rating
+    pub volatility: f64,
+}
+
+impl Default for Rating {
+    fn default() -> Self {
+        Rating {
+            rating: INITIAL_RATING,
+            deviation: INITIAL_DEVIATION,
+            volatility: INITIAL_VOLATILITY,
+        }
+    }
+}
+
+impl Rating {
+    /// Return the rating after a rating period with `results`, as (opponent rating, score) pairs.
+    /// The score is 1 for a win, 0.5 for a draw and 0 for a loss.
+    /// See [Glickman, Example of the Glicko-2 system](http://www.glicko.net/glicko/glicko2.pdf).
+    pub fn update(&self, results: &[(Rating, f64)]) -> Rating {
+        let mu = (self.rating - INITIAL_RATING) / GLICKO2_SCALE;
+        let phi = self.deviation / GLICKO2_SCALE;
+        let sigma = self.volatility;
+        if results.is_empty() {
+            let phi = (phi * phi + sigma * sigma).sqrt();
+            return Rating {
+                deviation: phi * GLICKO2_SCALE,
+                ..*self
+            };
+        }
+        let g = |phi: f64| 1.0 / (1.0 + 3.0 * phi * phi / (PI * PI)).sqrt();
+        let mut v_inv = 0.0;
+        let mut delta_sum = 0.0;
+        for (opponent, score) in results {
+            let mu_j = (opponent.rating - INITIAL_RATING) / GLICKO2_SCALE;
+            let g_j = g(opponent.deviation / GLICKO2_SCALE);
+            let e = 1.0 / (1.0 + (-g_j * (mu - mu_j)).exp());
+            v_inv += g_j * g_j * e * (1.0 - e);
+            delta_sum += g_j * (score - e);
+        }
+        let v = 1.0 / v_inv;
+        let delta = v * delta_sum;
+
+        // find the new volatility with the Illinois algorithm
+        let a = (sigma * sigma).ln();
+        let f = |x: f64| {
+            let ex = x.exp();
+            ex * (delta * delta - phi * phi - v - ex) / (2.0 * (phi * phi + v + ex).powi(2))
+                - (x - a) / (TAU * TAU)
+        };
+        let mut big_a = a;
+        let mut big_b = if delta * delta > phi * phi + v {
+            (delta * delta - phi * phi - v).ln()
+        } else {
+            let mut k = 1.0;
+            while f(a - k * TAU) < 0.0 {
+                k += 1.0;
+            }
+            a - k * TAU
+        };
+        let (mut f_a, mut f_b) = (f(big_a), f(big_b));
+        while (big_b - big_a).abs() > 1e-6 {
+            let big_c = big_a + (big_a - big_b) * f_a / (f_b - f_a);
+            let f_c = f(big_c);
+            if f_c * f_b <= 0.0 {
+                big_a = big_b;
+                f_a = f_b;
+            } else {
+                f_a /= 2.0;
+            }
+            big_b = big_c;
+            f_b = f_c;
+        }
+        let sigma = (big_a / 2.0).exp();
+
+        let phi_star = (phi * phi + sigma * sigma).sqrt();
+        let phi = 1.0 / (1.0 / (phi_star * phi_star) + 1.0 / v).sqrt();
+        let mu = mu + phi * phi * delta_sum;
+        Rating {
+            rating: mu * GLICKO2_SCALE + INITIAL_RATING,
+            deviation: phi * GLICKO2_SCALE,
+            volatility: sigma,
+        }
+    }
+}
+
+/// A game played in a [`Tournament`].
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct TournamentGame {
+    /// round of the tournament, starting at 0
+    pub round: usize,
+    /// index of the entrants: strategy 0 and 1 of the result
+    pub entrants: [usize; 2],
+    /// the result of the game
+    pub result: GameResult,
+}
+
+/// The results of an entrant in a [`Tournament`].
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Standing {
+    /// name of the entrant
+    pub name: String,
+    /// number of games played
+    pub games: usize,
+    /// number of games won, counting a draw as half a win
+    pub wins: f64,
+    /// sum of the spreads of the games
+    pub spread: i64,
+    /// Elo rating
+    pub elo: f64,
+    /// Glicko-2 rating
+    pub glicko: Rating,
+}
+
+/// The games and standings of a [`Tournament`].
+/// With the `serde` feature the result can be serialized, for example to JSON.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct TournamentResult {
+    /// every game, in the order they were played
+    pub games: Vec<TournamentGame>,
+    /// the standings of the entrants, in the order they were added to the tournament
+    pub standings: Vec<Standing>,
+}
+
+impl TournamentResult {
+    /// Return the standings, ranked by Glicko-2 rating
+    pub fn ranking(&self) -> Vec<&Standing> {
+        let mut ranking: Vec<&Standing> = self.standings.iter().collect();
+        ranking.sort_by(|a, b| b.glicko.rating.total_cmp(&a.glicko.rating));
+        ranking
+    }
+
+    /// Write the games as CSV, with a header line.
+    /// ## Errors
+    /// If writing fails.
+    pub fn write_games_csv<W: Write>(&self, mut w: W) -> io::Result<()> {
+        writeln!(
+            w,
+            "round,seed,first,player0,player1,score0,score1,moves0,moves1,bingos0,bingos1"
+        )?;
+        for game in &self.games {
+            let r = &game.result;
+            writeln!(
+                w,
+                "{},{},{},{},{},{},{},{},{},{},{}",
+                game.round,
+                r.seed,
+                r.first,
+                csv_field(&self.standings[game.entrants[0]].name),
+                csv_field(&self.standings[game.entrants[1]].name),
+                r.scores[0],
+                r.scores[1],
+                r.moves[0],
+                r.moves[1],
+                r.bingos[0],
+                r.bingos[1]
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Write the standings as CSV, with a header line.
+    /// ## Errors
+    /// If writing fails.
+    pub fn write_standings_csv<W: Write>(&self, mut w: W) -> io::Result<()> {
+        writeln!(
+            w,
+            "name,games,wins,spread,elo,glicko_rating,glicko_deviation,glicko_volatility"
+        )?;
+        for s in &self.standings {
+            writeln!(
+                w,
+                "{},{},{},{},{:.1},{:.1},{:.1},{:.6}",
+                csv_field(&s.name),
+                s.games,
+                s.wins,
+                s.spread,
+                s.elo,
+                s.glicko.rating,
+                s.glicko.deviation,
+                s.glicko.volatility
+            )?;
+        }
+        Ok(())
+    }
+}
+
+fn csv_field(s: &str) -> String {
+    if s.contains(&[',', '"', '\n'][..]) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        String::from(s)
+    }
+}
+
+/// A tournament between strategies, such as bots with different settings.
+///
+/// The entrants play `games` games against each opponent they are paired with,
+/// each with the same seeds, and with alternating first player, see [`play_match`].
+/// Every game is recorded, and the entrants are rated with Elo and Glicko-2 ratings,
+/// where each round is a Glicko-2 rating period.
+///
+/// ## Examples
+/// ```
+/// # use wordfeud_solver::{Board, Error, Greedy, Pairing, Tournament, Weakest};
+/// let board = Board::default().with_wordlist_from_words(&["rust", "rest", "at", "ta"])?;
+/// let result = Tournament::new(Pairing::RoundRobin, 2, 0)
+///     .with_entrant("greedy", Greedy)
+///     .with_entrant("weakest", Weakest)
+///     .run(&board)?;
+/// assert_eq!(result.games.len(), 2);
+/// let mut csv = Vec::new();
+/// result.write_standings_csv(&mut csv).unwrap();
+/// # Ok::<(), Error>(())
+/// ```
+pub struct Tournament {
+    pairing: Pairing,
+    games: usize,
+    seed: u64,
+    entrants: Vec<(String, Box<dyn Strategy + Sync>)>,
+}
+
+impl Tournament {
+    /// Create a tournament without entrants, where paired entrants play `games` games,
+    /// with seeds from `seed`.
+    pub fn new(pairing: Pairing, games: usize, seed: u64) -> Tournament {
+        Tournament {
+            pairing,
+            games,
+            seed,
+            entrants: Vec::new(),
+        }
+    }
+
+    /// Add an entrant with `name` that plays with `strategy`, and return the modified tournament.
+    #[must_use]
+    pub fn with_entrant<S: Strategy + Sync + 'static>(
+        mut self,
+        name: &str,
+        strategy: S,
+    ) -> Tournament {
+        self.entrants.push((String::from(name), Box::new(strategy)));
+        self
+    }
+
+    /// Play the tournament on `board`.
+    /// ## Errors
+    /// If a game fails, see [`play_game`](crate::play_game).
+    pub fn run(&self, board: &Board) -> Result<TournamentResult, Error> {
+        let n = self.entrants.len();
+        let mut standings: Vec<Standing> = self
+            .entrants
+            .iter()
+            .map(|(name, _)| Standing {
+                name: name.clone(),
+                games: 0,
+                wins: 0.0,
+                spread: 0,
+                elo: INITIAL_RATING,
+                glicko: Rating::default(),
+            })
+            .collect();
+        let mut games = Vec::new();
+        let mut played = vec![vec![false; n]; n];
+        let rounds = match self.pairing {
+            Pairing::RoundRobin => 1,
+            Pairing::Swiss { rounds } => rounds,
+        };
+        for round in 0..rounds {
+            let pairs = match self.pairing {
+                Pairing::RoundRobin => (0..n)
+                    .flat_map(|i| (i + 1..n).map(move |j| (i, j)))
+                    .collect(),
+                Pairing::Swiss { .. } => swiss_pairs(&standings, &played),
+            };
+            let mut period: Vec<Vec<(Rating, f64)>> = vec![Vec::new(); n];
+            for (i, j) in pairs {
+                played[i][j] = true;
+                played[j][i] = true;
+                let seed = self.seed.wrapping_add((round * self.games) as u64);
+                let report = play_match(
+                    board,
+                    self.entrants[i].1.as_ref(),
+                    self.entrants[j].1.as_ref(),
+                    self.games,
+                    seed,
+                )?;
+                for result in report.results {
+                    let score = match result.spread() {
+                        s if s > 0 => 1.0,
+                        s if s < 0 => 0.0,
+                        _ => 0.5,
+                    };
+                    for &(p, q, score, spread) in &[
+                        (i, j, score, result.spread()),
+                        (j, i, 1.0 - score, -result.spread()),
+                    ] {
+                        standings[p].games += 1;
+                        standings[p].wins += score;
+                        standings[p].spread += i64::from(spread);
+                        period[p].push((standings[q].glicko, score));
+                    }
+                    let expected =
+                        1.0 / (1.0 + 10_f64.powf((standings[j].elo - standings[i].elo) / 400.0));
+                    standings[i].elo += ELO_K * (score - expected);
+                    standings[j].elo -= ELO_K * (score - expected);
+                    games.push(TournamentGame {
+                        round,
+                        entrants: [i, j],
+                        result,
+                    });
+                }
+            }
+            for (standing, results) in standings.iter_mut().zip(&period) {
+                standing.glicko = standing.glicko.update(results);
+            }
+        }
+        Ok(TournamentResult { games, standings })
+    }
+}
+
+/// Pair the entrants with similar results, avoiding rematches if possible.
+/// Each entrant is paired with the best ranked entrant it did not play yet.
+/// Only if it played all entrants that are left, it gets a rematch with the best ranked of them.
+/// With an odd number of entrants, the lowest ranked entrant without a pair sits out.
+fn swiss_pairs(standings: &[Standing], played: &[Vec<bool>]) -> Vec<(usize, usize)> {
+    let mut order: Vec<usize> = (0..standings.len()).collect();
+    order.sort_by(|&a, &b| {
+        standings[b]
+            .wins
+            .total_cmp(&standings[a].wins)
+            .then_with(|| {
+                standings[b]
+                    .glicko
+                    .rating
+                    .total_cmp(&standings[a].glicko.rating)
+            })
+    });
+    let mut pairs = Vec::new();
+    while order.len() > 1 {
+        let i = order.remove(0);
+        // `i` is removed from `order`, so it is never paired with itself
+        let j = match order.iter().position(|&j| !played[i][j]) {
+            Some(fresh) => order.remove(fresh),
+            // `i` played all entrants that are left: a rematch with the best ranked of them
+            None => order.remove(0),
+        };
+        pairs.push((i, j));
+    }
+    pairs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::board;
+    use crate::{Greedy, RandomMove, Weakest};
+
+    type Result<T> = std::result::Result<T, Error>;
+
+    #[test]
+    fn test_glicko2() {
+        // the example from Glickman
+        let player = Rating {
+            rating: 1500.0,
+            deviation: 200.0,
+            volatility: 0.06,
+        };
+        let opponent = |rating, deviation| Rating {
+            rating,
+            deviation,
+            volatility: 0.06,
+        };
+        let results = [
+            (opponent(1400.0, 30.0), 1.0),
+            (opponent(1550.0, 100.0), 0.0),
+            (opponent(1700.0, 300.0), 0.0),
+        ];
+        let rating = player.update(&results);
+        assert!((rating.rating - 1464.06).abs() < 0.01);
+        assert!((rating.deviation - 151.52).abs() < 0.01);
+        assert!((rating.volatility - 0.05999).abs() < 0.00001);
+
+        // without games the deviation increases
+        assert!(player.update(&[]).deviation > player.deviation);
+    }
+
+    #[test]
+    fn test_swiss_pairs() {
+        let standings: Vec<Standing> = (0..4)
+            .map(|i| Standing {
+                name: i.to_string(),
+                games: 0,
+                wins: f64::from(3 - i),
+                spread: 0,
+                elo: INITIAL_RATING,
+                glicko: Rating::default(),
+            })
+            .collect();
+        let mut played = vec![vec![false; 4]; 4];
+        assert_eq!(swiss_pairs(&standings, &played), [(0, 1), (2, 3)]);
+
+        // a rematch is avoided
+        played[0][1] = true;
+        played[1][0] = true;
+        assert_eq!(swiss_pairs(&standings, &played), [(0, 2), (1, 3)]);
+
+        // the leader played everyone: a rematch with the next entrant
+        played[0] = vec![true; 4];
+        for row in &mut played[1..] {
+            row[0] = true;
+        }
+        assert_eq!(swiss_pairs(&standings, &played), [(0, 1), (2, 3)]);
+    }
+
+    #[test]
+    fn test_tournament() -> Result<()> {
+        let board = board()?;
+        for &pairing in &[Pairing::RoundRobin, Pairing::Swiss { rounds: 3 }] {
+            let result = Tournament::new(pairing, 4, 1)
+                .with_entrant("greedy", Greedy)
+                .with_entrant("random", RandomMove { seed: 2 })
+                .with_entrant("weakest", Weakest)
+                .run(&board)?;
+            let games: usize = result.standings.iter().map(|s| s.games).sum();
+            assert_eq!(games, 2 * result.games.len());
+            assert_eq!(result.ranking()[0].name, "greedy");
+            assert!(result.standings[0].elo > INITIAL_RATING);
+            assert!(result
+                .standings
+                .iter()
+                .all(|s| s.glicko.deviation < INITIAL_DEVIATION));
+
+            let mut csv = Vec::new();
+            result.write_games_csv(&mut csv).unwrap();
+            let csv = String::from_utf8(csv).unwrap();
+            assert_eq!(csv.lines().count(), result.games.len() + 1);
+            #[cfg(feature = "serde")]
+            {
+                let json = serde_json::to_string(&result).unwrap();
+                assert!(json.contains("\"name\":\"weakest\""));
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_escape() {
+        assert_eq!(csv_field("a,b"), "\"a,b\"");
+        assert_eq!(csv_field("greedy"), "greedy");
+    }
+}
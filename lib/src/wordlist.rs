@@ -1,17 +1,23 @@
+mod builder;
+mod gaddag;
+#[cfg(feature = "mmap")]
+mod mapped;
 mod matches;
-mod trievec;
+mod table;
 
-use self::trievec::TrieVec;
+use self::builder::Builder;
+pub use self::gaddag::{Gaddag, MoveGenerator};
+pub(crate) use self::table::{Node, Table};
 use crate::labelset::{Label, LabelSet};
 pub use crate::tiles::{Item, ItemList, List, Row};
 use crate::Codec;
 use crate::Error;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
-use std::collections::VecDeque;
 use std::convert::TryFrom;
 use std::fmt;
 use std::fs::read_to_string;
+use std::sync::{Arc, OnceLock};
 use tinyvec::ArrayVec;
 
 /// A set of letters
@@ -28,14 +34,16 @@ pub type RowData = ArrayVec<RowCache>;
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 /// A trie data structure that holds all the possible words.
+///
+/// The trie is minimized: nodes with the same children are shared, so a node can have more than one parent.
 pub struct Wordlist {
-    /// List of nodes in trie. Each node is a tuple with the index of the first
-    /// child node, and a `CharSet` with the labels of all child nodes.
-    pub nodes: Vec<(u32, LabelSet)>,
+    /// List of nodes in trie. Each node has the index of the first
+    /// child node, and a `LabelSet` with the labels of all child nodes.
+    pub(crate) nodes: Table<Node>,
     /// List of labels.
-    pub labels: Vec<Label>,
+    pub(crate) labels: Table<Label>,
     /// List indicating terminal nodes
-    pub terminal: Vec<bool>,
+    pub(crate) terminal: Table<bool>,
     /// Path of the wordfile used to build the wordlist.
     /// Empty if the wordlist is not build from a file.
     pub wordfile: String,
@@ -45,62 +53,30 @@ pub struct Wordlist {
     pub word_count: usize,
     /// The number of nodes in the wordlist.
     pub node_count: usize,
+    /// The number of nodes the words would have in a trie without shared nodes.
+    /// It is not serialized, to keep the format of older wordlist files, so it is 0 after deserialization,
+    /// and then it is not shown by `Display`.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub trie_node_count: usize,
     /// Encode words to/from labelvec
     pub codec: Codec,
+    /// The GADDAG with the same words, built on first use by [`gaddag`](Wordlist::gaddag).
+    #[cfg_attr(feature = "serde", serde(skip))]
+    gaddag: OnceLock<Arc<Gaddag>>,
 }
 
 impl fmt::Display for Wordlist {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(
             f,
-            "<Wordlist: {} words, {} nodes from '{}'>",
-            self.word_count, self.node_count, self.wordfile
-        )
-    }
-}
-
-impl From<TrieVec<Label>> for Wordlist {
-    fn from(trie: TrieVec<Label>) -> Self {
-        let mut nodes: Vec<(u32, LabelSet)> = Vec::new();
-        let mut labels: Vec<Label> = Vec::new();
-        let mut terminal: Vec<bool> = Vec::new();
-        let mut word_count = 0;
-        let mut node_count = 0;
-        let mut all_labels: LabelSet = LabelSet::new();
-        let codec = Codec::default(); // TODO
-
-        let mut i: usize = 0;
-        let mut queue = VecDeque::new();
-        queue.push_back((&trie, i, 0));
-        while let Some((node, index, k)) = queue.pop_front() {
-            let mut ls = LabelSet::new();
-            for (label, t) in node.children() {
-                ls.insert(*label);
-                all_labels.insert(*label);
-                queue.push_back((t, i, *label));
-            }
-            if node.terminal() {
-                word_count += 1;
-            }
-            nodes.push((0, ls));
-            terminal.push(node.terminal());
-            labels.push(k);
-            if nodes[index].0 == 0 {
-                nodes[index].0 = i as u32;
-            }
-            i += 1;
-            node_count += 1;
-        }
-        Wordlist {
-            nodes,
-            labels,
-            terminal,
-            wordfile: String::new(),
-            all_labels,
-            word_count,
-            node_count,
-            codec,
+            "<Wordlist: {} words, {} nodes",
+            self.word_count, self.node_count
+        )?;
+        // the count is not known for a deserialized wordlist
+        if self.trie_node_count > 0 {
+            write!(f, " ({} before minimization)", self.trie_node_count)?;
         }
+        write!(f, " from '{}'>", self.wordfile)
     }
 }
 
@@ -132,20 +108,20 @@ impl<'a> Iterator for IteratorChildren<'a> {
 impl Wordlist {
     /// Read the wordlist from a file. The file must be encoded in utf-8 and
     /// have one word per line.
+    ///
+    /// The wordlist is built fastest if the words are sorted, otherwise they are sorted first.
     /// ## Errors
     /// Fails if the wordlist can not be read, or a word can not be encoded.
     pub fn from_file(wordfile: &str, codec: &Codec) -> Result<Wordlist, Error> {
-        let mut builder = TrieVec::new();
-        read_to_string(wordfile)
+        let words = read_to_string(wordfile)
             .map_err(|source| Error::ReadError {
                 path: String::from(wordfile),
                 source,
             })?
             .lines()
-            .map(str::trim)
-            .map(|word| codec.encode(&word).map(|labels| builder.insert(&labels)))
-            .collect::<Result<Vec<()>, Error>>()?;
-        let mut wordlist = Wordlist::from(builder);
+            .map(|word| codec.encode(word.trim()))
+            .collect::<Result<Vec<_>, Error>>()?;
+        let mut wordlist = Builder::build(words);
         wordlist.wordfile = String::from(wordfile);
         wordlist.codec = codec.clone();
         Ok(wordlist)
@@ -155,11 +131,11 @@ impl Wordlist {
     /// ## Errors
     /// If a word can not be encoded with the given `codec`.
     pub fn from_words(words: &[&str], codec: &Codec) -> Result<Wordlist, Error> {
-        let mut builder = TrieVec::new();
-        for &word in words {
-            builder.insert(codec.encode(word)?);
-        }
-        let mut wordlist = Wordlist::from(builder);
+        let words = words
+            .iter()
+            .map(|word| codec.encode(word))
+            .collect::<Result<Vec<_>, Error>>()?;
+        let mut wordlist = Builder::build(words);
         wordlist.codec = codec.clone();
         Ok(wordlist)
     }
@@ -197,12 +173,22 @@ impl Wordlist {
         self.codec.decode(&items.codes()).join("")
     }
 
+    /// Return the [`Gaddag`] with the words of the wordlist.
+    ///
+    /// The GADDAG is built on the first call, and then shared: boards that use the same
+    /// `Arc<Wordlist>` with [`MoveGenerator::Gaddag`] build it only once.
+    pub fn gaddag(&self) -> Arc<Gaddag> {
+        self.gaddag
+            .get_or_init(|| Arc::new(Gaddag::from(self)))
+            .clone()
+    }
+
     /// Return the start and end index of the child nodes of node `i`,
     /// or None if node is empty.
     pub fn range_children(&self, i: usize) -> Option<(usize, usize)> {
-        let (start, labels) = &self.nodes[i];
-        let s = *start as usize;
-        let len = labels.len();
+        let node = &self.nodes[i];
+        let s = node.first_child as usize;
+        let len = node.labels.len();
         match len {
             0 => None,
             n => Some((s, s + n - 1)),
@@ -210,7 +196,7 @@ impl Wordlist {
     }
 
     /// Iterate over the children of node `i`.
-    pub fn iter_children(&self, i: usize) -> IteratorChildren {
+    pub fn iter_children(&self, i: usize) -> IteratorChildren<'_> {
         IteratorChildren {
             wordlist: self,
             range: self.range_children(i),
@@ -220,20 +206,30 @@ impl Wordlist {
 
     /// Get the index of child with `label` for node `i` if present.
     pub fn get(&self, i: usize, label: Label) -> Option<usize> {
-        let (start, labels) = &self.nodes[i];
-        if let Some(index) = labels.index_of(label) {
-            return Some(*start as usize + index);
+        let node = &self.nodes[i];
+        if let Some(index) = node.labels.index_of(label) {
+            return Some(node.first_child as usize + index);
         }
         None
     }
 
+    /// Return the label of node `i`.
+    pub fn label(&self, i: usize) -> Label {
+        self.labels[i]
+    }
+
+    /// Returns true if a word ends at node `i`.
+    pub fn is_terminal(&self, i: usize) -> bool {
+        self.terminal[i]
+    }
+
     /// Returns true if `word` is in wordlist
     pub fn is_word<K: AsRef<[Label]>>(&self, word: K) -> bool {
         let mut i = 0;
         for c in word.as_ref() {
-            let (start, labels) = &self.nodes[i];
-            if let Some(pos) = labels.index_of(*c) {
-                i = *start as usize + pos;
+            let node = &self.nodes[i];
+            if let Some(pos) = node.labels.index_of(*c) {
+                i = node.first_child as usize + pos;
             } else {
                 return false;
             }
@@ -270,6 +266,71 @@ mod test {
         assert_eq!(wordlist.range_children(4), None);
     }
 
+    #[test]
+    fn test_minimize() {
+        let words = &["bust", "must", "rust", "rusts", "ru"];
+        let mut wordlist = Wordlist::from_words(words, &Codec::default()).unwrap();
+        // The trie has 14 nodes: the root, b-u-s-t, m-u-s-t and r-u-s-t-s.
+        // The children of "b" and "m" are the same block: u, with s, with a terminal t.
+        // It is stored once, so the 3 nodes of "ust" after "m" are shared with "bust": 14 - 3 = 11 nodes.
+        // The children of "r" differ, because "ru" is a word and "rust" continues with "s".
+        assert_eq!(wordlist.trie_node_count, 14);
+        assert_eq!(wordlist.node_count, 11);
+        assert_eq!(wordlist.word_count, 5);
+        for &word in words {
+            let w: Word = wordlist.encode(word).unwrap();
+            assert!(wordlist.is_word(w.codes()));
+        }
+        for &word in &["bu", "mus", "busts", "r"] {
+            let w: Word = wordlist.encode(word).unwrap();
+            assert!(!wordlist.is_word(w.codes()));
+        }
+        assert!(wordlist
+            .to_string()
+            .starts_with("<Wordlist: 5 words, 11 nodes (14 before minimization)"));
+        // a deserialized wordlist has no trie node count
+        wordlist.trie_node_count = 0;
+        assert_eq!(
+            wordlist.to_string(),
+            "<Wordlist: 5 words, 11 nodes from ''>"
+        );
+    }
+
+    #[test]
+    fn test_builder() {
+        // sorted, unsorted and duplicate words give the same wordlist
+        let codec = Codec::default();
+        let words = [
+            "", "bust", "must", "rust", "rusts", "ru", "af", "a", "b", "rust", "ahs",
+        ];
+        let mut sorted = words.to_vec();
+        sorted.sort_unstable();
+        let mut unique = sorted.clone();
+        unique.dedup();
+        let expect = Wordlist::from_words(&unique, &codec).unwrap();
+        for words in &[&words[..], &sorted[..]] {
+            let wordlist = Wordlist::from_words(words, &codec).unwrap();
+            assert_eq!(&*wordlist.nodes, &*expect.nodes);
+            assert_eq!(&*wordlist.labels, &*expect.labels);
+            assert_eq!(&*wordlist.terminal, &*expect.terminal);
+            assert_eq!(wordlist.all_labels, expect.all_labels);
+            assert_eq!(wordlist.word_count, expect.word_count);
+            assert_eq!(wordlist.trie_node_count, expect.trie_node_count);
+        }
+        // the duplicate "rust" is counted once
+        assert_eq!(expect.word_count, 10);
+        for &word in &words[1..] {
+            let w: Word = expect.encode(word).unwrap();
+            assert!(expect.is_word(w.codes()));
+        }
+        for &word in &["ah", "bu", "rus", "abs", "rustss"] {
+            let w: Word = expect.encode(word).unwrap();
+            assert!(!expect.is_word(w.codes()));
+        }
+        let wordlist = Wordlist::from_words(&[], &codec).unwrap();
+        assert_eq!((wordlist.word_count, wordlist.node_count), (0, 1));
+    }
+
     #[test]
     fn test_terminal() {
         let wordlist = test_wordlist();
@@ -0,0 +1,186 @@
+use super::{Node, Wordlist};
+use crate::labelset::{Label, LabelSet};
+use crate::Codec;
+use std::collections::{HashMap, VecDeque};
+use std::sync::OnceLock;
+
+/// A child in an open block: label, terminal flag, and the start and labels of its own (closed) block
+type Entry = (Label, bool, u32, LabelSet);
+
+/// The key of a closed block: label, terminal flag and start of the block of children of each child
+type BlockKey = Vec<(Label, bool, u32)>;
+
+/// Build a minimized wordlist from words that are inserted in sorted order.
+///
+/// The words of the current path are kept in open blocks, one for each depth.
+/// When a word does not share the prefix of the previous word anymore, the blocks below the
+/// common prefix are complete: they are closed, and stored once in the flattened layout.
+/// Blocks with the same children are only stored once, so the result is minimized.
+pub(crate) struct Builder {
+    /// The children of the nodes on the path of the previous word, the root first
+    open: Vec<Vec<Entry>>,
+    previous: Vec<Label>,
+    register: HashMap<BlockKey, u32>,
+    /// Closed blocks in the flattened layout; index 0 is reserved for the root
+    nodes: Vec<Node>,
+    labels: Vec<Label>,
+    terminal: Vec<bool>,
+    root_terminal: bool,
+    word_count: usize,
+    trie_node_count: usize,
+}
+
+impl Builder {
+    pub fn new() -> Builder {
+        Builder {
+            open: vec![Vec::new()],
+            previous: Vec::new(),
+            register: HashMap::new(),
+            nodes: vec![Node::default()],
+            labels: vec![0],
+            terminal: vec![false],
+            root_terminal: false,
+            word_count: 0,
+            trie_node_count: 1,
+        }
+    }
+
+    /// Build a wordlist from `words`. The words are sorted first if needed.
+    pub fn build(mut words: Vec<Vec<Label>>) -> Wordlist {
+        if !words.windows(2).all(|w| w[0] <= w[1]) {
+            words.sort_unstable();
+        }
+        let mut builder = Builder::new();
+        for word in &words {
+            builder.insert(word);
+        }
+        builder.finish()
+    }
+
+    /// Insert `word`, that must not be smaller than the previous word.
+    pub fn insert(&mut self, word: &[Label]) {
+        debug_assert!(self.previous.as_slice() <= word, "words are not sorted");
+        let prefix = word
+            .iter()
+            .zip(&self.previous)
+            .take_while(|(a, b)| a == b)
+            .count();
+        if prefix == word.len() && prefix == self.previous.len() && self.word_count > 0 {
+            return; // duplicate
+        }
+        self.close(prefix);
+        for &label in &word[prefix..] {
+            self.open
+                .last_mut()
+                .unwrap()
+                .push((label, false, 0, LabelSet::new()));
+            self.open.push(Vec::new());
+        }
+        self.trie_node_count += word.len() - prefix;
+        match self.open.len() {
+            1 => self.root_terminal = true,
+            n => self.open[n - 2].last_mut().unwrap().1 = true,
+        }
+        self.word_count += 1;
+        self.previous.clear();
+        self.previous.extend_from_slice(word);
+    }
+
+    /// Close the open blocks below `depth`.
+    fn close(&mut self, depth: usize) {
+        while self.open.len() > depth + 1 {
+            let block = self.open.pop().unwrap();
+            let start = self.store(&block);
+            let labels = block.iter().map(|entry| entry.0).collect();
+            let parent = self.open.last_mut().unwrap().last_mut().unwrap();
+            parent.2 = start;
+            parent.3 = labels;
+        }
+    }
+
+    /// Store a closed block, and return its start index.
+    fn store(&mut self, block: &[Entry]) -> u32 {
+        if block.is_empty() {
+            return 0;
+        }
+        let key: BlockKey = block
+            .iter()
+            .map(|&(label, terminal, start, _)| (label, terminal, start))
+            .collect();
+        let nodes = &mut self.nodes;
+        let labels = &mut self.labels;
+        let terminal = &mut self.terminal;
+        *self.register.entry(key).or_insert_with(|| {
+            let start = nodes.len() as u32;
+            for &(label, is_terminal, first_child, child_labels) in block {
+                nodes.push(Node {
+                    first_child,
+                    labels: child_labels,
+                });
+                labels.push(label);
+                terminal.push(is_terminal);
+            }
+            start
+        })
+    }
+
+    /// Close all blocks, and lay out the nodes breadth first.
+    pub fn finish(mut self) -> Wordlist {
+        self.close(0);
+        let root = self.open.pop().unwrap();
+        let root = Node {
+            first_child: self.store(&root),
+            labels: root.iter().map(|entry| entry.0).collect(),
+        };
+
+        // The blocks are stored in the order they are closed, with the leaves first.
+        // Lay them out in breadth first order, so the nodes near the root are close together.
+        let mut nodes = vec![Node {
+            first_child: 0,
+            labels: root.labels,
+        }];
+        let mut labels = vec![0];
+        let mut terminal = vec![self.root_terminal];
+        let mut all_labels = LabelSet::new();
+        let mut start: Vec<Option<u32>> = vec![None; self.nodes.len()];
+        let mut queue = VecDeque::new();
+        queue.push_back((root, 0));
+        while let Some((node, parent)) = queue.pop_front() {
+            if node.labels.is_empty() {
+                continue;
+            }
+            let block = node.first_child as usize;
+            if let Some(index) = start[block] {
+                nodes[parent].first_child = index;
+                continue;
+            }
+            let index = nodes.len();
+            start[block] = Some(index as u32);
+            nodes[parent].first_child = index as u32;
+            for k in 0..node.labels.len() {
+                let child = self.nodes[block + k];
+                all_labels.insert(self.labels[block + k]);
+                nodes.push(Node {
+                    first_child: 0,
+                    labels: child.labels,
+                });
+                labels.push(self.labels[block + k]);
+                terminal.push(self.terminal[block + k]);
+                queue.push_back((child, index + k));
+            }
+        }
+        let node_count = nodes.len();
+        Wordlist {
+            nodes: nodes.into(),
+            labels: labels.into(),
+            terminal: terminal.into(),
+            wordfile: String::new(),
+            all_labels,
+            word_count: self.word_count,
+            node_count,
+            trie_node_count: self.trie_node_count,
+            codec: Codec::default(),
+            gaddag: OnceLock::new(),
+        }
+    }
+}
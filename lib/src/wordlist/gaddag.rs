@@ -0,0 +1,572 @@
+use super::builder::Builder;
+use super::matches::start_indices;
+use super::{Node, RowData, Table, Wordlist};
+use crate::labelset::Label;
+use crate::tiles::{Cell, Letters, List, Row, Tile, Word};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Label of the separator between the reversed prefix and the suffix of a word
+const SEPARATOR: Label = 0;
+
+/// How a [`Board`](crate::Board) generates the words that can be played in a row.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MoveGenerator {
+    /// walk the [`Wordlist`] trie from each start position, see [`Wordlist::words`]
+    #[default]
+    Trie,
+    /// walk a [`Gaddag`] from each anchor, see [`Gaddag::words`]
+    Gaddag,
+}
+
+/// A GADDAG: a trie with every word once for each of its letters, as the reversed prefix up to the letter,
+/// a separator, and the suffix after the letter.
+///
+/// With a GADDAG the words in a row are generated from the squares where a word must connect (the anchors),
+/// first extending to the left and then to the right, without exploring the same prefixes from every start position.
+///
+/// ## Examples
+/// ```
+/// # use wordfeud_solver::{Codec, Error, Gaddag, RowData, Wordlist};
+/// let wordlist = Wordlist::from_words(&["af", "ah", "al", "bar", "bel"], &Codec::default())?;
+/// let gaddag = Gaddag::from(&wordlist);
+/// let row = wordlist.encode("    a  ")?;
+/// let rowdata: RowData = wordlist.connected_row(&row);
+/// let letters = wordlist.encode("fhl")?;
+/// let mut words: Vec<String> = gaddag
+///     .words(&row, &rowdata, &letters, None)
+///     .map(|(_, word)| wordlist.decode(word))
+///     .collect();
+/// words.sort();
+/// assert_eq!(words, ["af", "ah", "al"]);
+/// # Ok::<(), Error>(())
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Gaddag {
+    /// List of nodes, with the index of the first child node and the labels of all child nodes.
+    nodes: Table<Node>,
+    /// List indicating terminal nodes
+    terminal: Table<bool>,
+    /// The number of words
+    pub word_count: usize,
+    /// The number of nodes
+    pub node_count: usize,
+}
+
+impl fmt::Display for Gaddag {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "<Gaddag: {} words, {} nodes>",
+            self.word_count, self.node_count
+        )
+    }
+}
+
+impl From<&Wordlist> for Gaddag {
+    /// Build a GADDAG with the words of `wordlist`.
+    ///
+    /// Every word of n letters gives n GADDAG paths. The paths are sorted and fed to the builder
+    /// one first label at a time, so only the paths that start with one label are in memory,
+    /// at the cost of a walk over the words of the wordlist for each label.
+    fn from(wordlist: &Wordlist) -> Self {
+        let mut builder = Builder::new();
+        let mut paths: Vec<Vec<Label>> = Vec::new();
+        for first in wordlist.all_labels.iter() {
+            // the first label of a path is the letter before the separator
+            for_each_word(wordlist, |word| {
+                for i in (1..=word.len()).filter(|&i| word[i - 1] == first) {
+                    let mut path: Vec<Label> = word[..i].iter().rev().cloned().collect();
+                    path.push(SEPARATOR);
+                    path.extend_from_slice(&word[i..]);
+                    paths.push(path);
+                }
+            });
+            paths.sort_unstable();
+            for path in &paths {
+                builder.insert(path);
+            }
+            paths.clear();
+        }
+        let paths = builder.finish();
+        Gaddag {
+            nodes: paths.nodes,
+            terminal: paths.terminal,
+            word_count: wordlist.word_count,
+            node_count: paths.node_count,
+        }
+    }
+}
+
+/// Call `f` with every word of `wordlist`, depth first.
+fn for_each_word<F: FnMut(&[Label])>(wordlist: &Wordlist, mut f: F) {
+    // the word of the node on top of the stack is in `word`
+    let mut word: Vec<Label> = Vec::new();
+    let mut stack: Vec<(usize, usize)> = vec![(0, 0)];
+    while let Some((node, depth)) = stack.pop() {
+        word.truncate(depth.saturating_sub(1));
+        if depth > 0 {
+            word.push(wordlist.labels[node]);
+        }
+        if wordlist.terminal[node] && !word.is_empty() {
+            f(&word);
+        }
+        stack.extend(
+            wordlist
+                .iter_children(node)
+                .map(|(_, child)| (child, depth + 1)),
+        );
+    }
+}
+
+impl Gaddag {
+    /// Get the index of child with `label` for node `i` if present.
+    fn get(&self, i: usize, label: Label) -> Option<usize> {
+        let node = &self.nodes[i];
+        node.labels
+            .index_of(label)
+            .map(|index| node.first_child as usize + index)
+    }
+
+    /// Return the words that can be placed in `row` with `letters`, with their start position.
+    /// Like [`Wordlist::words`], a word starts at most `maxdist` squares before a tile or connected square,
+    /// with the number of letters as default. The words are generated lazily.
+    ///
+    /// The result is the same as that of [`Wordlist::words`] for the same words, but in a different order.
+    pub fn words<'a>(
+        &'a self,
+        row: &Row,
+        rowdata: &'a RowData,
+        letters: &Letters,
+        maxdist: Option<usize>,
+    ) -> impl Iterator<Item = (usize, Word)> + 'a {
+        Generator::new(self, *row, rowdata, *letters, maxdist)
+    }
+}
+
+/// Maximum length of a row
+const N_MAX: usize = 16;
+
+/// A step of the generation of the words from an anchor
+enum Step {
+    /// Cover square `pos`, left of or at `anchor`. `left` holds the tiles from `anchor` down to `pos + 1`.
+    Left {
+        anchor: usize,
+        pos: usize,
+        node: usize,
+        letters: Letters,
+        left: Word,
+    },
+    /// Continue after covering square `pos`: extend further to the left, or start the word at `pos`.
+    Covered {
+        anchor: usize,
+        pos: usize,
+        node: usize,
+        letters: Letters,
+        left: Word,
+    },
+    /// Cover square `pos`, right of the anchor, for the word starting at `start`.
+    Right {
+        start: usize,
+        pos: usize,
+        node: usize,
+        letters: Letters,
+        word: Word,
+    },
+}
+
+/// Iterator over the words of a row, with a stack of the steps that are left.
+struct Generator<'a> {
+    gaddag: &'a Gaddag,
+    row: Row,
+    rowdata: &'a RowData,
+    anchors: [bool; N_MAX],
+    /// the squares where a word may start
+    starts: [bool; N_MAX],
+    /// the first square where a word may start: the generation does not extend further to the left
+    first_start: usize,
+    stack: Vec<Step>,
+}
+
+impl<'a> Generator<'a> {
+    fn new(
+        gaddag: &'a Gaddag,
+        row: Row,
+        rowdata: &'a RowData,
+        letters: Letters,
+        maxdist: Option<usize>,
+    ) -> Generator<'a> {
+        let mut anchors = [false; N_MAX];
+        for pos in 0..row.len() {
+            anchors[pos] = row[pos].is_empty()
+                && (rowdata[pos].1
+                    || (pos > 0 && !row[pos - 1].is_empty())
+                    || (pos + 1 < row.len() && !row[pos + 1].is_empty()));
+        }
+        let mut extended = row;
+        extended.push(Cell::EMPTY);
+        let mut starts = [false; N_MAX];
+        for pos in start_indices(extended, rowdata, maxdist.unwrap_or_else(|| letters.len())) {
+            if pos < row.len() {
+                starts[pos] = true;
+            }
+        }
+        let first_start = starts.iter().position(|&start| start).unwrap_or(N_MAX);
+        let stack = (0..row.len())
+            .rev()
+            .filter(|&anchor| anchors[anchor] && anchor >= first_start)
+            .map(|anchor| Step::Left {
+                anchor,
+                pos: anchor,
+                node: 0,
+                letters,
+                left: Word::new(),
+            })
+            .collect();
+        Generator {
+            gaddag,
+            row,
+            rowdata,
+            anchors,
+            starts,
+            first_start,
+            stack,
+        }
+    }
+
+    /// Return the tiles that can be placed at `pos` from `letters`, from node `node`,
+    /// with the child node and the remaining letters.
+    fn placements(
+        &self,
+        pos: usize,
+        node: usize,
+        letters: &Letters,
+    ) -> Vec<(Tile, usize, Letters)> {
+        let valid_chars = self.rowdata[pos].0;
+        let mut result = Vec::new();
+        for (i, &letter) in letters.iter().enumerate() {
+            if letters[0..i].contains(&letter) {
+                continue;
+            }
+            let next_letters = letters.remove(i);
+            if letter.is_blank() {
+                let Node {
+                    first_child,
+                    labels,
+                } = self.gaddag.nodes[node];
+                for (k, label) in labels.iter().enumerate() {
+                    if label != SEPARATOR && valid_chars.contains(label) {
+                        let tile = Tile::wildcard_from_letter(label);
+                        result.push((tile, first_child as usize + k, next_letters));
+                    }
+                }
+            } else if valid_chars.contains(letter.label()) {
+                if let Some(child) = self.gaddag.get(node, letter.label()) {
+                    result.push((Tile::from_letter(letter), child, next_letters));
+                }
+            }
+        }
+        result
+    }
+
+    fn extend_left(
+        &mut self,
+        anchor: usize,
+        pos: usize,
+        node: usize,
+        letters: Letters,
+        left: Word,
+    ) {
+        if let Some(tile) = self.row[pos].tile() {
+            if let Some(child) = self.gaddag.get(node, tile.label()) {
+                let mut left = left;
+                left.push(tile);
+                self.stack.push(Step::Covered {
+                    anchor,
+                    pos,
+                    node: child,
+                    letters,
+                    left,
+                });
+            }
+            return;
+        }
+        // a word is generated from its leftmost anchor
+        if pos != anchor && self.anchors[pos] {
+            return;
+        }
+        for (tile, child, next_letters) in self.placements(pos, node, &letters) {
+            let mut left = left;
+            left.push(tile);
+            self.stack.push(Step::Covered {
+                anchor,
+                pos,
+                node: child,
+                letters: next_letters,
+                left,
+            });
+        }
+    }
+
+    fn covered_left(
+        &mut self,
+        anchor: usize,
+        pos: usize,
+        node: usize,
+        letters: Letters,
+        left: Word,
+    ) {
+        // the word can not start left of the first start
+        let can_extend = pos > self.first_start;
+        if pos > 0 && !self.row[pos - 1].is_empty() {
+            // the word includes the tiles to the left
+            if can_extend {
+                self.stack.push(Step::Left {
+                    anchor,
+                    pos: pos - 1,
+                    node,
+                    letters,
+                    left,
+                });
+            }
+            return;
+        }
+        if can_extend && !letters.is_empty() {
+            self.stack.push(Step::Left {
+                anchor,
+                pos: pos - 1,
+                node,
+                letters,
+                left,
+            });
+        }
+        if self.starts[pos] {
+            if let Some(child) = self.gaddag.get(node, SEPARATOR) {
+                let word: Word = left.iter().rev().cloned().collect();
+                self.stack.push(Step::Right {
+                    start: pos,
+                    pos: anchor + 1,
+                    node: child,
+                    letters,
+                    word,
+                });
+            }
+        }
+    }
+
+    /// Returns the word if it ends before `pos`.
+    fn extend_right(
+        &mut self,
+        start: usize,
+        pos: usize,
+        node: usize,
+        letters: Letters,
+        word: Word,
+    ) -> Option<(usize, Word)> {
+        let tile = if pos < self.row.len() {
+            self.row[pos].tile()
+        } else {
+            None
+        };
+        if let Some(tile) = tile {
+            if let Some(child) = self.gaddag.get(node, tile.label()) {
+                let mut word = word;
+                word.push(tile);
+                self.stack.push(Step::Right {
+                    start,
+                    pos: pos + 1,
+                    node: child,
+                    letters,
+                    word,
+                });
+            }
+            return None;
+        }
+        if pos < self.row.len() {
+            for (tile, child, next_letters) in self.placements(pos, node, &letters) {
+                let mut next = word;
+                next.push(tile);
+                self.stack.push(Step::Right {
+                    start,
+                    pos: pos + 1,
+                    node: child,
+                    letters: next_letters,
+                    word: next,
+                });
+            }
+        }
+        if self.gaddag.terminal[node] && word.len() > 1 {
+            Some((start, word))
+        } else {
+            None
+        }
+    }
+}
+
+impl<'a> Iterator for Generator<'a> {
+    type Item = (usize, Word);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(step) = self.stack.pop() {
+            match step {
+                Step::Left {
+                    anchor,
+                    pos,
+                    node,
+                    letters,
+                    left,
+                } => self.extend_left(anchor, pos, node, letters, left),
+                Step::Covered {
+                    anchor,
+                    pos,
+                    node,
+                    letters,
+                    left,
+                } => self.covered_left(anchor, pos, node, letters, left),
+                Step::Right {
+                    start,
+                    pos,
+                    node,
+                    letters,
+                    word,
+                } => {
+                    if let Some(word) = self.extend_right(start, pos, node, letters, word) {
+                        return Some(word);
+                    }
+                }
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Board, Codec, Game, Greedy, Language, Strategy};
+    use std::collections::HashSet;
+
+    type Result<T> = std::result::Result<T, crate::Error>;
+
+    const WORDS: &[&str] = &[
+        "af", "ah", "al", "aar", "aas", "be", "bi", "bo", "bar", "bes", "bel", "belt", "de", "do",
+        "en", "er", "et", "in", "is", "na", "ne", "no", "nu", "of", "op", "re", "so", "te", "to",
+        "ze", "zo", "ren", "ton", "net", "ster", "rest", "rust", "toen", "tien", "stern", "kerst",
+        "vrees", "beter", "hadden", "zeer", "elf",
+    ];
+
+    const TEST_STATE: &[&str] = &[
+        "    t     c   f",
+        "    e    he   o",
+        "    r   bis g k",
+        "    u  bol te v",
+        "    gepof dimme",
+        "      la vree e",
+        "    qua   ene  ",
+        "      Spoelen  ",
+        "     s a   n   ",
+        "     c d we    ",
+        "     hadden    ",
+        "    nu o   y   ",
+        "  wrat siJzen  ",
+        "    k     os   ",
+        "   zerk   g    ",
+    ];
+
+    #[test]
+    fn test_gaddag() -> Result<()> {
+        let wordlist = Wordlist::from_words(&["af", "bar", "bel"], &Codec::default())?;
+        let gaddag = Gaddag::from(&wordlist);
+        assert_eq!(gaddag.word_count, 3);
+        // "bar" is stored as "b+ar", "ab+r" and "rab+",
+        // and the paths of all words are stored, whatever their first label
+        for path in &[
+            &[2, SEPARATOR, 1, 18][..],
+            &[1, 2, SEPARATOR, 18],
+            &[18, 1, 2, SEPARATOR],
+            &[1, SEPARATOR, 6],
+            &[6, 1, SEPARATOR],
+            &[2, SEPARATOR, 5, 12],
+            &[5, 2, SEPARATOR, 12],
+            &[12, 5, 2, SEPARATOR],
+        ] {
+            let node = path
+                .iter()
+                .try_fold(0, |node, &label| gaddag.get(node, label));
+            assert!(node.is_some_and(|node| gaddag.terminal[node]));
+        }
+        assert_eq!(gaddag.get(0, SEPARATOR), None);
+        Ok(())
+    }
+
+    /// Compare the words of the GADDAG with the words of the trie, for all rows of a board,
+    /// and return the number of words.
+    fn compare(board: &Board, letters: &str, maxdist: Option<usize>) -> Result<usize> {
+        let mut count = 0;
+        let wordlist = board.wordlist();
+        let gaddag = Gaddag::from(wordlist);
+        let letters: Letters = board.encode(letters)?;
+        for &horizontal in &[false, true] {
+            let state = if horizontal {
+                board.horizontal()
+            } else {
+                board.vertical()
+            };
+            for (row, rowdata) in state.iter().zip(board.rowdata(horizontal)) {
+                let expected: Vec<(usize, Word)> =
+                    wordlist.words(row, rowdata, &letters, maxdist).collect();
+                let found: Vec<(usize, Word)> =
+                    gaddag.words(row, rowdata, &letters, maxdist).collect();
+                let key = |words: &[(usize, Word)]| -> HashSet<(usize, Vec<u8>)> {
+                    words
+                        .iter()
+                        .map(|(pos, word)| (*pos, word.codes()))
+                        .collect()
+                };
+                assert_eq!(found.len(), expected.len());
+                assert_eq!(key(&found), key(&expected));
+                count += found.len();
+            }
+        }
+        Ok(count)
+    }
+
+    #[test]
+    fn test_same_words() -> Result<()> {
+        let board = Board::new(Language::NL).with_wordlist_from_words(WORDS)?;
+        assert!(compare(&board, "rustbel", None)? > 0);
+        assert!(compare(&board, "bet*n", None)? > 0);
+        let board = board.with_state_from_strings(TEST_STATE)?;
+        for letters in &["abel", "rustbel", "zeer*", "*e*", "nnnoqz"] {
+            for &maxdist in &[None, Some(1), Some(2)] {
+                compare(&board, letters, maxdist)?;
+            }
+        }
+        assert!(compare(&board, "abel", None)? > compare(&board, "abel", Some(1))?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_board_move_generator() -> Result<()> {
+        let board = Board::new(Language::NL).with_wordlist_from_words(WORDS)?;
+        let mut game = Game::new(board, 2, 3)?;
+        let mut gaddag_board = game
+            .board()
+            .clone()
+            .with_move_generator(MoveGenerator::Gaddag);
+        assert_eq!(gaddag_board.move_generator(), MoveGenerator::Gaddag);
+        while !game.is_over() {
+            let mut expected = game.board().calc_all_word_scores(game.rack())?;
+            gaddag_board.set_state(&game.board().horizontal());
+            let mut found = gaddag_board.calc_all_word_scores(game.rack())?;
+            expected.sort();
+            found.sort();
+            assert_eq!(found, expected);
+            let action = Greedy.choose(&game.view())?;
+            game.play(action)?;
+        }
+        Ok(())
+    }
+}
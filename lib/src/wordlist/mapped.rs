@@ -0,0 +1,354 @@
+//! A binary wordlist file that is memory-mapped and used in place.
+//!
+//! The file starts with a header of `HEADER_SIZE` bytes, followed by the tables of the wordlist:
+//!
+//! | offset | size | field                                         |
+//! |--------|------|-----------------------------------------------|
+//! | 0      | 8    | magic `b"WFSDAWG\0"`                          |
+//! | 8      | 4    | format version                                |
+//! | 12     | 4    | byte order mark `0x01020304`                  |
+//! | 16     | 8    | node count `n`                                |
+//! | 24     | 8    | word count                                    |
+//! | 32     | 8    | node count before minimization                |
+//! | 40     | 4    | all labels                                    |
+//! | 44     | 4    | CRC-32 of the tables                          |
+//! | 48     | 4    | CRC-32 of the alphabet of the codec           |
+//! | 52     | 12   | reserved, zero                                |
+//! | 64     | 8n   | nodes: first child (u32) and labels (u32)     |
+//! | 64+8n  | n    | labels (u8)                                   |
+//! | 64+9n  | n    | terminal flags (u8, 0 or 1)                   |
+//!
+//! Integers are stored in the byte order of the machine that wrote the file;
+//! a file with a different byte order is rejected on open.
+//! The file does not store the codec, but a file is rejected if it is opened with a codec
+//! with another alphabet than the codec of the wordlist that wrote it.
+use super::{Node, Table, Wordlist};
+use crate::labelset::{Label, LabelSet};
+use crate::{Codec, Error};
+use memmap2::Mmap;
+use std::fs::{self, File};
+use std::io::{BufWriter, Write};
+use std::mem::size_of;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::sync::OnceLock;
+
+const MAGIC: &[u8; 8] = b"WFSDAWG\0";
+const VERSION: u32 = 1;
+const BYTE_ORDER: u32 = 0x0102_0304;
+const HEADER_SIZE: usize = 64;
+const NODE_SIZE: usize = size_of::<Node>();
+
+/// Number of temporary files written by this process, to give each a unique name
+static TMP_FILES: AtomicUsize = AtomicUsize::new(0);
+
+/// A fingerprint of the alphabet of `codec`, to check that a file is opened with the right codec
+fn fingerprint(codec: &Codec) -> u32 {
+    crc32fast::hash(codec.alphabet().as_bytes())
+}
+
+fn read_u32(bytes: &[u8], offset: usize) -> u32 {
+    let mut buf = [0; 4];
+    buf.copy_from_slice(&bytes[offset..offset + 4]);
+    u32::from_ne_bytes(buf)
+}
+
+fn read_u64(bytes: &[u8], offset: usize) -> u64 {
+    let mut buf = [0; 8];
+    buf.copy_from_slice(&bytes[offset..offset + 8]);
+    u64::from_ne_bytes(buf)
+}
+
+/// The tables of a wordlist as they are stored in the file, without the header
+fn tables(wordlist: &Wordlist) -> Vec<u8> {
+    let n = wordlist.nodes.len();
+    let mut bytes = Vec::with_capacity(n * (NODE_SIZE + 2));
+    for node in wordlist.nodes.iter() {
+        bytes.extend_from_slice(&node.first_child.to_ne_bytes());
+        bytes.extend_from_slice(&u32::from(node.labels).to_ne_bytes());
+    }
+    bytes.extend_from_slice(&wordlist.labels);
+    bytes.extend(wordlist.terminal.iter().map(|&t| t as u8));
+    bytes
+}
+
+/// Check that the children of every node are in range, and have the labels of the node,
+/// and that no node is its own descendant, so every walk over the trie ends.
+fn check_nodes(nodes: &[Node], labels: &[Label]) -> Result<(), String> {
+    for (i, node) in nodes.iter().enumerate() {
+        let start = node.first_child as usize;
+        if start + node.labels.len() > nodes.len() {
+            return Err(format!("children of node {} out of range", i));
+        }
+        if !node
+            .labels
+            .iter()
+            .zip(&labels[start..])
+            .all(|(label, &child)| label == child)
+        {
+            return Err(format!("children of node {} do not match its labels", i));
+        }
+    }
+    check_acyclic(nodes)
+}
+
+/// Check that the nodes have no cycles, with Kahn's algorithm:
+/// repeatedly remove a node without parents, until all nodes are removed.
+/// The children of every node must be in range.
+fn check_acyclic(nodes: &[Node]) -> Result<(), String> {
+    let children = |node: &Node| {
+        let start = node.first_child as usize;
+        start..start + node.labels.len()
+    };
+    let mut parents = vec![0u32; nodes.len()];
+    for node in nodes {
+        for child in children(node) {
+            parents[child] += 1;
+        }
+    }
+    let mut free: Vec<usize> = (0..nodes.len()).filter(|&i| parents[i] == 0).collect();
+    let mut removed = 0;
+    while let Some(i) = free.pop() {
+        removed += 1;
+        for child in children(&nodes[i]) {
+            parents[child] -= 1;
+            if parents[child] == 0 {
+                free.push(child);
+            }
+        }
+    }
+    if removed < nodes.len() {
+        return Err(String::from("nodes have a cycle"));
+    }
+    Ok(())
+}
+
+impl Wordlist {
+    /// Returns true if the wordlist uses a memory-mapped file,
+    /// see [`from_mapped_file`](Wordlist::from_mapped_file).
+    pub fn is_mapped(&self) -> bool {
+        self.nodes.is_mapped()
+    }
+
+    /// Write the wordlist to `path` in a binary format that can be opened with
+    /// [`from_mapped_file`](Wordlist::from_mapped_file).
+    ///
+    /// The file is written next to `path` and then renamed, so processes that have mapped
+    /// an older version of `path` keep using the old contents.
+    /// ## Errors
+    /// If the file can not be written.
+    pub fn write_mapped_file(&self, path: &str) -> Result<(), Error> {
+        let tables = tables(self);
+        let mut header = Vec::with_capacity(HEADER_SIZE);
+        header.extend_from_slice(MAGIC);
+        header.extend_from_slice(&VERSION.to_ne_bytes());
+        header.extend_from_slice(&BYTE_ORDER.to_ne_bytes());
+        header.extend_from_slice(&(self.nodes.len() as u64).to_ne_bytes());
+        header.extend_from_slice(&(self.word_count as u64).to_ne_bytes());
+        header.extend_from_slice(&(self.trie_node_count as u64).to_ne_bytes());
+        header.extend_from_slice(&u32::from(self.all_labels).to_ne_bytes());
+        header.extend_from_slice(&crc32fast::hash(&tables).to_ne_bytes());
+        header.extend_from_slice(&fingerprint(&self.codec).to_ne_bytes());
+        header.resize(HEADER_SIZE, 0);
+
+        let write_error = |source| Error::WriteError {
+            path: String::from(path),
+            source,
+        };
+        let tmp = format!(
+            "{}.{}-{}.tmp",
+            path,
+            std::process::id(),
+            TMP_FILES.fetch_add(1, Ordering::Relaxed)
+        );
+        let mut writer = BufWriter::new(File::create(&tmp).map_err(write_error)?);
+        writer.write_all(&header).map_err(write_error)?;
+        writer.write_all(&tables).map_err(write_error)?;
+        writer.flush().map_err(write_error)?;
+        fs::rename(&tmp, path).map_err(write_error)
+    }
+
+    /// Open a wordlist file written by [`write_mapped_file`](Wordlist::write_mapped_file).
+    ///
+    /// The file is memory-mapped and the wordlist uses it in place, without reading it into memory.
+    /// All wordlists that map the same file, also in other processes, share its pages.
+    /// The file must not be modified while it is mapped, replace it with a new file instead.
+    ///
+    /// On open, the header, the checksum and the structure of the trie are validated.
+    /// ## Errors
+    /// - If the file can not be read.
+    /// - If the file is not a valid wordlist file, or has a different version.
+    /// - If the file was written with a codec with another alphabet than `codec`.
+    pub fn from_mapped_file(path: &str, codec: &Codec) -> Result<Wordlist, Error> {
+        let invalid = |reason: String| Error::InvalidWordfile {
+            path: String::from(path),
+            reason,
+        };
+        let file = File::open(path).map_err(|source| Error::ReadError {
+            path: String::from(path),
+            source,
+        })?;
+        // Safe as long as the file is not modified while it is mapped, see above
+        let map = unsafe { Mmap::map(&file) }.map_err(|source| Error::ReadError {
+            path: String::from(path),
+            source,
+        })?;
+
+        if map.len() < HEADER_SIZE || &map[..MAGIC.len()] != MAGIC {
+            return Err(invalid(String::from("no wordlist header")));
+        }
+        if read_u32(&map, 12) != BYTE_ORDER {
+            return Err(invalid(String::from("wrong byte order")));
+        }
+        let version = read_u32(&map, 8);
+        if version != VERSION {
+            return Err(invalid(format!(
+                "version {} is not supported (expect {})",
+                version, VERSION
+            )));
+        }
+        let n = read_u64(&map, 16) as usize;
+        let size = n
+            .checked_mul(NODE_SIZE + 2)
+            .and_then(|size| size.checked_add(HEADER_SIZE));
+        if n == 0 || size != Some(map.len()) {
+            return Err(invalid(format!(
+                "size {} does not match {} nodes",
+                map.len(),
+                n
+            )));
+        }
+        if crc32fast::hash(&map[HEADER_SIZE..]) != read_u32(&map, 44) {
+            return Err(invalid(String::from("checksum mismatch")));
+        }
+        if read_u32(&map, 48) != fingerprint(codec) {
+            return Err(invalid(String::from(
+                "written with a codec with another alphabet",
+            )));
+        }
+        let word_count = read_u64(&map, 24) as usize;
+        let trie_node_count = read_u64(&map, 32) as usize;
+        let all_labels = LabelSet::from(read_u32(&map, 40));
+        let labels_offset = HEADER_SIZE + n * NODE_SIZE;
+        let terminal_offset = labels_offset + n;
+        if map[terminal_offset..].iter().any(|&t| t > 1) {
+            return Err(invalid(String::from("invalid terminal flag")));
+        }
+
+        let map = Arc::new(map);
+        // Safe: the map is page aligned, so the nodes at HEADER_SIZE are aligned,
+        // the size of the tables is checked above, any bit pattern is a valid `Node` or `Label`,
+        // and the terminal flags are checked to be 0 or 1.
+        let (nodes, labels, terminal): (Table<Node>, Table<Label>, Table<bool>) = unsafe {
+            (
+                Table::mapped(map.clone(), HEADER_SIZE, n),
+                Table::mapped(map.clone(), labels_offset, n),
+                Table::mapped(map, terminal_offset, n),
+            )
+        };
+        check_nodes(&nodes, &labels).map_err(invalid)?;
+        Ok(Wordlist {
+            nodes,
+            labels,
+            terminal,
+            wordfile: String::from(path),
+            all_labels,
+            word_count,
+            node_count: n,
+            trie_node_count,
+            codec: codec.clone(),
+            gaddag: OnceLock::new(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tiles::Word;
+
+    const WORDS: &[&str] = &[
+        "af", "ah", "al", "aar", "aas", "bi", "bo", "bar", "bes", "bel", "belt",
+    ];
+
+    fn reason(result: Result<Wordlist, Error>) -> String {
+        match result {
+            Err(Error::InvalidWordfile { reason, .. }) => reason,
+            other => panic!(
+                "expect InvalidWordfile, got {:?}",
+                other.map(|w| w.to_string())
+            ),
+        }
+    }
+
+    #[test]
+    fn test_mapped_file() -> Result<(), Error> {
+        let codec = Codec::default();
+        let wordlist = Wordlist::from_words(WORDS, &codec)?;
+        let path = std::env::temp_dir().join(format!("wordfeud_mapped_{}.bin", std::process::id()));
+        let path = path.to_str().unwrap();
+        wordlist.write_mapped_file(path)?;
+
+        let mapped = Wordlist::from_mapped_file(path, &codec)?;
+        assert!(mapped.is_mapped());
+        assert!(!wordlist.is_mapped());
+        assert_eq!(&*mapped.nodes, &*wordlist.nodes);
+        assert_eq!(&*mapped.labels, &*wordlist.labels);
+        assert_eq!(&*mapped.terminal, &*wordlist.terminal);
+        assert_eq!(mapped.all_labels, wordlist.all_labels);
+        assert_eq!(mapped.word_count, WORDS.len());
+        assert_eq!(mapped.trie_node_count, wordlist.trie_node_count);
+        for &word in WORDS {
+            let w: Word = mapped.encode(word)?;
+            assert!(mapped.is_word(w.codes()));
+        }
+        // the file must not be modified while it is mapped
+        drop(mapped);
+
+        let other = Codec::new(&["ä"]);
+        assert_eq!(
+            reason(Wordlist::from_mapped_file(path, &other)),
+            "written with a codec with another alphabet"
+        );
+
+        // corrupt the file in several ways
+        let bytes = fs::read(path).unwrap();
+        let corrupt = |offset: usize, value: u8| {
+            let mut bytes = bytes.clone();
+            bytes[offset] = value;
+            fs::write(path, bytes).unwrap();
+            Wordlist::from_mapped_file(path, &codec)
+        };
+        assert_eq!(reason(corrupt(0, b'X')), "no wordlist header");
+        assert_eq!(
+            reason(corrupt(8, 2)),
+            "version 2 is not supported (expect 1)"
+        );
+        assert_eq!(reason(corrupt(HEADER_SIZE + 1, 1)), "checksum mismatch");
+        fs::write(path, &bytes[..bytes.len() - 1]).unwrap();
+        assert!(reason(Wordlist::from_mapped_file(path, &codec)).starts_with("size"));
+        fs::remove_file(path).unwrap();
+        Ok(())
+    }
+
+    #[test]
+    fn test_check_nodes() {
+        let wordlist = Wordlist::from_words(WORDS, &Codec::default()).unwrap();
+        assert!(check_nodes(&wordlist.nodes, &wordlist.labels).is_ok());
+        let mut labels = wordlist.labels.to_vec();
+        labels[1] = 3;
+        assert!(check_nodes(&wordlist.nodes, &labels).is_err());
+        let mut nodes = wordlist.nodes.to_vec();
+        nodes[1].first_child = wordlist.node_count as u32;
+        assert!(check_nodes(&nodes, &wordlist.labels).is_err());
+
+        // a node that is its own child
+        let mut nodes = wordlist.nodes.to_vec();
+        nodes[1].first_child = 1;
+        nodes[1].labels = LabelSet::from(vec![wordlist.labels[1]]);
+        assert_eq!(
+            check_nodes(&nodes, &wordlist.labels),
+            Err(String::from("nodes have a cycle"))
+        );
+    }
+}
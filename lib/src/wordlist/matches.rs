@@ -118,6 +118,36 @@ impl<'a> Matches<'a> {
     }
 }
 
+/// Returns the indices in `row` where a word can start, given the connection data in `rowdata`,
+/// see [`Wordlist::start_indices`].
+pub(crate) fn start_indices(row: Row, rowdata: &RowData, maxdist: usize) -> Vec<usize> {
+    // For each cell in row, calculate the distance to the nearest connection point.
+    // A distance of DIM (16) indicates that the cell can not be a starting point.
+    // A word can not start in the cell next to a letter.
+    let mut d = DIM;
+    let mut dist: [usize; DIM] = [DIM; DIM];
+    for i in (0..rowdata.len()).rev() {
+        if !row[i].is_empty() {
+            // the distance is 0 if there is a tile in the cell
+            d = 0;
+        } else if rowdata[i].1 {
+            // if the cell is connected we need 1 letter
+            d = 1;
+        }
+        dist[i] = if i > 0 && !row[i - 1].is_empty() {
+            DIM
+        } else {
+            d
+        };
+        d += 1;
+    }
+    // return the indices where dist <= maxdist
+    dist.iter()
+        .enumerate()
+        .filter_map(|(i, d)| if *d <= maxdist { Some(i) } else { None })
+        .collect()
+}
+
 impl Wordlist {
     /// Return a list of matching words.
     pub fn matches<'a>(
@@ -143,31 +173,7 @@ impl Wordlist {
     /// Returns the indices in `row` where a word can start, given the connection data in `rowdata`.
     /// `maxdist` is the maximum distance for connecting, typically the number of letters we have.
     pub fn start_indices(&self, row: Row, rowdata: &RowData, maxdist: usize) -> Vec<usize> {
-        // For each cell in row, calculate the distance to the nearest connection point.
-        // A distance of DIM (16) indicates that the cell can not be a starting point.
-        // A word can not start in the cell next to a letter.
-        let mut d = DIM;
-        let mut dist: [usize; DIM] = [DIM; DIM];
-        for i in (0..rowdata.len()).rev() {
-            if !row[i].is_empty() {
-                // the distance is 0 if there is a tile in the cell
-                d = 0;
-            } else if rowdata[i].1 {
-                // if the cell is connected we need 1 letter
-                d = 1;
-            }
-            dist[i] = if i > 0 && !row[i - 1].is_empty() {
-                DIM
-            } else {
-                d
-            };
-            d += 1;
-        }
-        // return the indices where dist <= maxdist
-        dist.iter()
-            .enumerate()
-            .filter_map(|(i, d)| if *d <= maxdist { Some(i) } else { None })
-            .collect()
+        start_indices(row, rowdata, maxdist)
     }
 
     pub fn words<'a>(
@@ -183,7 +189,7 @@ impl Wordlist {
         let indices = self.start_indices(row, rowdata, maxdist);
         // println!("i {:?}", indices);
         indices.into_iter().flat_map(move |pos| {
-            self.matches(0, row, rowdata, pos, &letters)
+            self.matches(0, row, rowdata, pos, letters)
                 .map(move |word| (pos, word))
         })
     }
@@ -0,0 +1,106 @@
+use crate::labelset::LabelSet;
+#[cfg(feature = "mmap")]
+use memmap2::Mmap;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::ops::Deref;
+#[cfg(feature = "mmap")]
+use std::sync::Arc;
+
+/// A node in the flattened trie: the index of the first child node,
+/// and a `LabelSet` with the labels of all child nodes.
+///
+/// The layout is fixed, so the nodes of a memory-mapped wordlist file can be used in place.
+/// Changing it changes the file format, and needs a new `VERSION` in `mapped.rs`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[repr(C)]
+pub(crate) struct Node {
+    /// Index of the first child node
+    pub first_child: u32,
+    /// The labels of the child nodes
+    pub labels: LabelSet,
+}
+
+/// A read-only table with the items of a wordlist.
+///
+/// The items are either owned, or live in a memory-mapped wordlist file
+/// that is shared with all wordlists (and processes) that map the same file.
+#[derive(Clone)]
+pub(crate) struct Table<T>(Storage<T>);
+
+#[derive(Clone)]
+enum Storage<T> {
+    /// Items in memory
+    Owned(Vec<T>),
+    /// `len` items at byte `offset` in a memory-mapped file
+    #[cfg(feature = "mmap")]
+    Mapped {
+        map: Arc<Mmap>,
+        offset: usize,
+        len: usize,
+    },
+}
+
+#[cfg(feature = "mmap")]
+impl<T> Table<T> {
+    /// Return a table with `len` items of type `T` at byte `offset` in `map`.
+    /// ## Safety
+    /// The items must be in bounds and aligned, and every item must be a valid `T`.
+    pub(crate) unsafe fn mapped(map: Arc<Mmap>, offset: usize, len: usize) -> Table<T> {
+        debug_assert!(offset + len * std::mem::size_of::<T>() <= map.len());
+        debug_assert_eq!(
+            (map.as_ptr() as usize + offset) % std::mem::align_of::<T>(),
+            0
+        );
+        Table(Storage::Mapped { map, offset, len })
+    }
+
+    /// Returns true if the items live in a memory-mapped file.
+    pub(crate) fn is_mapped(&self) -> bool {
+        matches!(self.0, Storage::Mapped { .. })
+    }
+}
+
+impl<T> Deref for Table<T> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        match &self.0 {
+            Storage::Owned(items) => items,
+            #[cfg(feature = "mmap")]
+            // Safe, because `Table::mapped` is only called with valid items,
+            // and the map lives as long as the table.
+            Storage::Mapped { map, offset, len } => unsafe {
+                std::slice::from_raw_parts(map.as_ptr().add(*offset) as *const T, *len)
+            },
+        }
+    }
+}
+
+impl<T> From<Vec<T>> for Table<T> {
+    fn from(items: Vec<T>) -> Self {
+        Table(Storage::Owned(items))
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Table<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T: Serialize> Serialize for Table<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        (**self).serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for Table<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Vec::deserialize(deserializer).map(Table::from)
+    }
+}
@@ -1,6 +1,7 @@
 use pyo3::create_exception;
 use pyo3::{basic::PyObjectProtocol, exceptions::PyException, prelude::*, PyErr};
 use std::convert::From;
+use std::sync::Arc;
 use wordfeud_solver::Language;
 
 create_exception!(pywordfeud_solver, WordfeudException, PyException);
@@ -47,6 +48,39 @@ struct BestScore {
     opp_score_diff: i32,
 }
 
+fn language(lang: &str) -> PyResult<Language> {
+    match lang {
+        "NL" => Ok(Language::NL),
+        "EN" => Ok(Language::EN),
+        "SE" => Ok(Language::SE),
+        _ => Err(PyErr::new::<WordfeudException, String>(format!(
+            "unsupported language: {}",
+            lang
+        ))),
+    }
+}
+
+/// A wordlist that can be shared by many boards of the same language.
+#[pyclass]
+struct Wordlist {
+    lang: String,
+    wordlist: Arc<wordfeud_solver::Wordlist>,
+}
+
+#[pymethods]
+impl Wordlist {
+    #[new]
+    fn new(lang: &str, wordfile: &str) -> PyResult<Self> {
+        let board = wordfeud_solver::Board::new(language(lang)?);
+        let wordlist = wordfeud_solver::Wordlist::from_file(wordfile, board.codec())
+            .map_err(WordfeudError::from)?;
+        Ok(Wordlist {
+            lang: String::from(lang),
+            wordlist: Arc::new(wordlist),
+        })
+    }
+}
+
 #[pyclass]
 struct Board {
     _board: wordfeud_solver::Board<'static>,
@@ -55,27 +89,36 @@ struct Board {
 #[pymethods]
 impl Board {
     #[new]
-    #[args(wordfile = "None", state = "None", grid = "None")]
+    #[args(wordfile = "None", state = "None", grid = "None", wordlist = "None")]
     fn new(
         lang: &str,
         wordfile: Option<&str>,
         state: Option<Vec<&str>>,
         grid: Option<Vec<&str>>,
+        wordlist: Option<&Wordlist>,
     ) -> PyResult<Self> {
-        let language = match lang {
-            "NL" => Ok(Language::NL),
-            "EN" => Ok(Language::EN),
-            "SE" => Ok(Language::SE),
-            _ => Err(PyErr::new::<WordfeudException, String>(format!(
-                "unsupported language: {}",
-                lang
-            ))),
-        }?;
-        let mut board = wordfeud_solver::Board::<'static>::new(language);
-        if let Some(wordfile) = wordfile {
-            board = board
-                .with_wordlist_from_file(wordfile)
-                .map_err(WordfeudError::from)?;
+        let mut board = wordfeud_solver::Board::<'static>::new(language(lang)?);
+        match (wordfile, wordlist) {
+            (Some(_), Some(_)) => {
+                return Err(PyErr::new::<WordfeudException, _>(
+                    "specify either wordfile or wordlist",
+                ))
+            }
+            (Some(wordfile), None) => {
+                board = board
+                    .with_wordlist_from_file(wordfile)
+                    .map_err(WordfeudError::from)?;
+            }
+            (None, Some(wordlist)) => {
+                if wordlist.lang != lang {
+                    return Err(PyErr::new::<WordfeudException, String>(format!(
+                        "wordlist language {} does not match board language {}",
+                        wordlist.lang, lang
+                    )));
+                }
+                board = board.with_wordlist(wordlist.wordlist.clone());
+            }
+            (None, None) => {}
         }
         if let Some(state) = state {
             board = board
@@ -222,7 +265,6 @@ impl Board {
                 played: s.played,
                 exit_flag: s.exit_flag as u8,
                 opp_score_diff: s.opp_score_diff,
-        
             })
             .collect();
         Ok(results)
@@ -234,7 +276,7 @@ struct WordfeudError(wordfeud_solver::Error);
 
 impl From<wordfeud_solver::Error> for WordfeudError {
     fn from(err: wordfeud_solver::Error) -> WordfeudError {
-        WordfeudError { 0: err }
+        WordfeudError(err)
     }
 }
 
@@ -251,6 +293,13 @@ impl PyObjectProtocol for Board {
     }
 }
 
+#[pyproto]
+impl PyObjectProtocol for Wordlist {
+    fn __repr__(&self) -> PyResult<String> {
+        Ok(self.wordlist.to_string())
+    }
+}
+
 #[pyproto]
 impl PyObjectProtocol for Score {
     fn __repr__(&self) -> String {
@@ -274,5 +323,6 @@ impl PyObjectProtocol for BestScore {
 #[pymodule]
 fn pywordfeud_solver(_py: Python, m: &PyModule) -> PyResult<()> {
     m.add_class::<Board>()?;
+    m.add_class::<Wordlist>()?;
     Ok(())
 }